@@ -1,45 +1,271 @@
+use std::f32::consts::TAU;
+
+use asteroid_drops::{DropTable, Drops};
+use asteroid_shape::AsteroidShape;
 use asteroid_spawner::SpawnAsteroids;
 use avian2d::{math::PI, prelude::*};
-use bevy::{prelude::*, utils::HashMap};
+use bevy::{
+    color::palettes::css::{DARK_GRAY, DARK_ORANGE, GOLD, INDIAN_RED, SLATE_GRAY, STEEL_BLUE},
+    ecs::system::SystemParam,
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+    utils::{HashMap, HashSet},
+};
 use bevy_asset_loader::prelude::*;
+use bevy_common_assets::ron::RonAssetPlugin;
 use bevy_transform_interpolation::*;
-use rand::{thread_rng, Rng};
+use rand::Rng;
+use serde::Deserialize;
 
 use crate::{
-    destruction::Destroyed, health_pickup::SpawnHealthPickup, projectile::{Shootable, Shot}, states::GameState, stats::{AngularAcceleration, Health, LinearAcceleration, Points, Score}, viewport_bound::DestroyOutOfBounds
+    destruction::Destroyed, explosion::Explode, faction::Faction, magnetism::MagneticField, pickup::SpawnPickup, projectile::{Shootable, Shot}, random::seeded_stream, run_config::RunConfig, states::GameState, stats::{AngularAcceleration, Health, LinearAcceleration, Points, Scoring}, viewport_bound::DestroyOutOfBounds, wave::{Wave, WaveStarted}
 };
 
+pub mod asteroid_drops;
+pub mod asteroid_shape;
 pub mod asteroid_spawner;
 
+/// The speed asteroids accelerate up to, and
+/// the speed the spawner assumes when predicting
+/// where a newly spawned asteroid will travel.
+const ASTEROID_MAX_SPEED: f32 = 200.;
+
 pub fn plugin(app: &mut App) {
+    app.add_plugins(RonAssetPlugin::<AsteroidConfig>::new(&["asteroids.ron"]));
     app.configure_loading_state(
         LoadingStateConfig::new(GameState::Loading).load_collection::<AsteroidAssets>(),
     );
     app.insert_resource(AsteroidID(0));
-    app.init_resource::<SmallAsteroidMap>();
-    app.register_type::<SmallAsteroidMap>();
+    app.init_resource::<AsteroidFamilies>();
+    app.register_type::<AsteroidFamilies>();
     app.register_type::<AsteroidID>();
+    app.init_resource::<AsteroidCollisionMode>();
+    app.register_type::<AsteroidCollisionMode>();
+    app.add_systems(OnExit(GameState::Loading), load_collision_mode);
+    app.register_type::<Armour>();
     app.observe(spawn_asteroid);
-    app.add_systems(FixedUpdate, move_asteroids);
-    app.add_systems(Update, (shot_asteroids, destroyed_asteroids).chain());
+    app.add_systems(
+        FixedUpdate,
+        (
+            convert_asteroid_bodies.run_if(resource_changed::<AsteroidCollisionMode>),
+            move_asteroids.run_if(resource_equals(AsteroidCollisionMode::PassThrough)),
+        )
+            .chain(),
+    );
+    app.add_systems(
+        Update,
+        (
+            shot_asteroids,
+            asteroid_impacts.run_if(resource_equals(AsteroidCollisionMode::Bounce)),
+            destroyed_asteroids,
+        )
+            .chain(),
+    );
+    app.add_systems(Update, visualize_armour);
+    app.add_plugins(asteroid_drops::plugin);
     app.add_plugins(asteroid_spawner::plugin);
 }
 
 #[derive(AssetCollection, Resource)]
 struct AsteroidAssets {
-    #[asset(key = "image.basic_asteroid")]
-    basic_asteroid: Handle<Image>,
-    #[asset(key = "image.small_basic_asteroid")]
-    small_basic_asteroid: Handle<Image>,
-    #[asset(key = "image.advanced_asteroid")]
-    advanced_asteroid: Handle<Image>,
-    #[asset(key = "image.small_advanced_asteroid")]
-    small_advanced_asteroid: Handle<Image>,
+    #[asset(key = "data.asteroids")]
+    config: Handle<AsteroidConfig>,
+}
+
+/// The per kind asteroid settings,
+/// loaded from `assets/data/game.asteroids.ron`.
+#[derive(Asset, TypePath, Deserialize, Debug)]
+struct AsteroidConfig {
+    /// What [`AsteroidCollisionMode`] the game starts in.
+    #[serde(default)]
+    collision_mode: AsteroidCollisionMode,
+    basic: AsteroidKindConfig,
+    advanced: AsteroidKindConfig,
+    volatile: AsteroidKindConfig,
+    magnetic: AsteroidKindConfig,
+    armoured: AsteroidKindConfig,
+}
+
+impl AsteroidConfig {
+    fn get(&self, kind: AsteroidKind) -> &AsteroidKindConfig {
+        match kind {
+            AsteroidKind::Basic => &self.basic,
+            AsteroidKind::Advanced => &self.advanced,
+            AsteroidKind::Volatile => &self.volatile,
+            AsteroidKind::Magnetic => &self.magnetic,
+            AsteroidKind::Armoured => &self.armoured,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug)]
+struct AsteroidKindConfig {
+    /// How many times the asteroid splits before
+    /// its pieces are destroyed for good.
+    depth: u8,
+    /// How many pieces each split creates.
+    split_count: u8,
+    /// The angle, in radians, between the direction
+    /// of neighbouring pieces of a split.
+    split_angle: f32,
+    /// The rough radius of a freshly generated asteroid;
+    /// the pieces split off of it are cut from its shape.
+    radius: f32,
+    /// How likely the spawners are to pick this kind,
+    /// relative to the weights of the other kinds.
+    spawn_weight: u32,
+    /// What else happens when an asteroid of
+    /// this kind is destroyed, besides splitting.
+    #[serde(default)]
+    on_death: Vec<DeathBehaviour>,
+    /// The field pulling the player and
+    /// projectiles in, if this kind has one.
+    #[serde(default)]
+    magnetic_field: Option<MagneticFieldConfig>,
+    /// The armour plating around the asteroid's
+    /// core, if this kind has any.
+    #[serde(default)]
+    armour: Option<ArmourConfig>,
+    /// What may be left behind when an asteroid
+    /// of this kind, or any piece of it, is destroyed.
+    #[serde(default)]
+    drops: DropTable,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+struct MagneticFieldConfig {
+    radius: f32,
+    strength: f32,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+struct ArmourConfig {
+    /// How wide, in radians, the exposed core is.
+    core_arc: f32,
+    /// The damage a shot to the armour deals.
+    armoured_damage: u16,
+}
+
+/// Armour plating covering all of an asteroid
+/// apart from an exposed core on one side.
+#[derive(Component, Reflect, Debug)]
+#[reflect(Component)]
+struct Armour {
+    /// The direction of the middle of the core,
+    /// in the asteroid's local space.
+    core_direction: Vec2,
+    core_arc: f32,
+    armoured_damage: u16,
+}
+
+impl Armour {
+    /// The damage a shot deals, depending on whether the
+    /// side it hit, given as a local space normal, is
+    /// part of the exposed core.
+    fn get_damage(&self, local_normal: Vec2) -> u16 {
+        if self.core_direction.angle_between(local_normal).abs() <= self.core_arc / 2. {
+            1
+        } else {
+            self.armoured_damage
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+enum DeathBehaviour {
+    /// Damage everything within `radius` by `damage`.
+    Explode { radius: f32, damage: u16 },
+}
+
+impl AsteroidKindConfig {
+    fn can_split(&self, tier: u8) -> bool {
+        tier < self.depth
+    }
+
+    /// The directions of `count` pieces of a split,
+    /// fanned out evenly around the parent's direction.
+    fn get_split_directions(&self, count: usize, direction: Vec3) -> impl Iterator<Item = Vec3> + '_ {
+        let middle = (count as f32 - 1.) / 2.;
+        (0..count).map(move |n| {
+            Quat::from_rotation_z((n as f32 - middle) * self.split_angle)
+                .mul_vec3(direction)
+                .normalize_or_zero()
+        })
+    }
+}
+
+/// Look up the loaded [`AsteroidKindConfig`] of an [`AsteroidKind`],
+/// alongside the [`RunConfig`], which modifies every kind.
+#[derive(SystemParam)]
+struct AsteroidSettings<'w> {
+    assets: Res<'w, AsteroidAssets>,
+    configs: Res<'w, Assets<AsteroidConfig>>,
+    run_config: Res<'w, RunConfig>,
+}
+
+impl AsteroidSettings<'_> {
+    fn get(&self, kind: AsteroidKind) -> &AsteroidKindConfig {
+        self.configs
+            .get(&self.assets.config)
+            .expect("the asteroid config is loaded before leaving the loading state")
+            .get(kind)
+    }
+}
+
+/// How asteroids interact with each other.
+///
+/// Starts out as set in the asteroid config. Changing it
+/// converts the asteroids already in play as well.
+#[derive(Resource, Reflect, Deserialize, Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+pub enum AsteroidCollisionMode {
+    /// Asteroids are kinematic bodies that
+    /// steer themselves and pass through each other.
+    #[default]
+    PassThrough,
+    /// Asteroids are dynamic bodies, weighted by their size,
+    /// that bounce off of each other and are damaged by hard impacts.
+    Bounce,
+}
+
+impl AsteroidCollisionMode {
+    /// Make the asteroid the body this mode calls
+    /// for, heading off at `velocity`.
+    fn insert_body(self, asteroid: &mut EntityCommands, velocity: Vec2) {
+        const ASTEROID_DENSITY: f32 = 1.;
+        const ASTEROID_RESTITUTION: f32 = 0.8;
+
+        match self {
+            AsteroidCollisionMode::PassThrough => {
+                asteroid.insert((RigidBody::Kinematic, LinearVelocity(velocity)));
+            }
+            AsteroidCollisionMode::Bounce => {
+                asteroid.insert((
+                    RigidBody::Dynamic,
+                    LinearVelocity(velocity),
+                    // The mass comes from the collider's area,
+                    // so bigger asteroids are heavier.
+                    ColliderDensity(ASTEROID_DENSITY),
+                    Restitution::new(ASTEROID_RESTITUTION),
+                ));
+            }
+        }
+    }
+}
+
+fn load_collision_mode(
+    mut collision_mode: ResMut<AsteroidCollisionMode>,
+    assets: Res<AsteroidAssets>,
+    configs: Res<Assets<AsteroidConfig>>,
+) {
+    if let Some(config) = configs.get(&assets.config) {
+        *collision_mode = config.collision_mode;
+    }
 }
 
 #[derive(Resource, Reflect, Default)]
 #[reflect(Resource)]
-struct AsteroidID(pub usize);
+pub struct AsteroidID(pub usize);
 
 impl AsteroidID {
     pub fn new() -> Self {
@@ -65,69 +291,153 @@ impl AsteroidID {
     }
 }
 
+/// Every asteroid spawned by a spawner starts a family,
+/// which all the pieces split off of it share the ID of.
+///
+/// Once every member of a family, however deep the tree
+/// of splits goes, has been destroyed, a new asteroid
+/// is spawned in to replace them.
 #[derive(Resource, Default, Reflect)]
 #[reflect(Resource)]
-struct SmallAsteroidMap(HashMap<usize, u16>);
+struct AsteroidFamilies(HashMap<usize, AsteroidFamily>);
+
+#[derive(Reflect, Debug)]
+struct AsteroidFamily {
+    /// How many members of the family are still alive.
+    alive: u16,
+}
+
+impl Default for AsteroidFamily {
+    fn default() -> Self {
+        Self { alive: 1 }
+    }
+}
+
+impl AsteroidFamilies {
+    /// Record a member of the family being destroyed,
+    /// and the pieces it split into replacing it.
+    ///
+    /// Returns `true` if that was the last member of the family.
+    fn destroy_member(&mut self, id: usize, pieces: u16) -> bool {
+        let family = self.0.entry(id).or_default();
+        family.alive = family.alive.saturating_sub(1) + pieces;
+
+        if family.alive == 0 {
+            self.0.remove(&id);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// The [`AsteroidFamilies`], and the [`Wave`]
+/// that clearing them brings to an end.
+#[derive(SystemParam)]
+struct FamilyProgress<'w> {
+    families: ResMut<'w, AsteroidFamilies>,
+    wave: ResMut<'w, Wave>,
+    wave_started_event_writer: EventWriter<'w, WaveStarted>,
+}
+
+impl FamilyProgress<'_> {
+    /// Record a member of a family being destroyed, as
+    /// [`AsteroidFamilies::destroy_member`] does, and if
+    /// that cleared the family, count it towards the wave.
+    ///
+    /// Returns `true` if the family was cleared.
+    fn destroy_member(&mut self, id: usize, pieces: u16) -> bool {
+        if !self.families.destroy_member(id, pieces) {
+            return false;
+        }
+
+        if self.wave.clear_family() {
+            self.wave_started_event_writer.send(WaveStarted(self.wave.number()));
+        }
+        true
+    }
+}
 
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct Asteroid {
     id: usize,
     direction: Vec3,
+    /// How many times this asteroid's family
+    /// has split to get to this piece; 0 for
+    /// a freshly spawned asteroid.
+    tier: u8,
+    /// The direction of the last projectile to hit the asteroid.
+    impact: Vec2,
+    /// What the asteroid's own randomness is drawn from, so
+    /// that it doesn't depend on when the asteroid is hit.
+    seed: u64,
+}
+
+impl Asteroid {
+    /// The stream its shape and armour are drawn from.
+    const SPAWN_STREAM: u64 = 0xA57E_501D;
+    /// The stream its split, and its pieces' seeds, are drawn from.
+    const SPLIT_STREAM: u64 = 0x5B17_C075;
+
+    /// The velocity the asteroid steers itself towards.
+    fn cruising_velocity(&self, speed_scale: f32) -> Vec2 {
+        (self.direction * ASTEROID_MAX_SPEED * speed_scale).xy()
+    }
 }
 
-#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[derive(Component, Reflect, Deserialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[reflect(Component)]
-enum AsteroidKind {
+pub enum AsteroidKind {
     Basic,
-    SmallBasic,
     Advanced,
-    SmallAdvanced,
+    Volatile,
+    Magnetic,
+    Armoured,
 }
 
 impl AsteroidKind {
-    pub fn get_name(&self) -> String {
-        String::from(match self {
+    pub const ALL: [AsteroidKind; 5] = [
+        AsteroidKind::Basic,
+        AsteroidKind::Advanced,
+        AsteroidKind::Volatile,
+        AsteroidKind::Magnetic,
+        AsteroidKind::Armoured,
+    ];
+
+    pub fn get_name(&self, tier: u8) -> String {
+        let size = match tier {
+            0 => "",
+            1 => "Medium",
+            _ => "Small",
+        };
+
+        let kind = match self {
             AsteroidKind::Basic => "BasicAsteroid",
-            AsteroidKind::SmallBasic => "SmallBasicAsteroid",
             AsteroidKind::Advanced => "AdvancedAsteroid",
-            AsteroidKind::SmallAdvanced => "SmallAdvancedAsteroid",
-        })
-    }
-    pub fn get_health(&self) -> u16 {
-        match self {
-            AsteroidKind::Basic | AsteroidKind::SmallBasic => 1,
-            AsteroidKind::Advanced | AsteroidKind::SmallAdvanced => 3,
-        }
-    }
+            AsteroidKind::Volatile => "VolatileAsteroid",
+            AsteroidKind::Magnetic => "MagneticAsteroid",
+            AsteroidKind::Armoured => "ArmouredAsteroid",
+        };
 
-    fn get_texture(&self, assets: Res<AsteroidAssets>) -> Handle<Image> {
-        match self {
-            AsteroidKind::Basic => assets.basic_asteroid.clone(),
-            AsteroidKind::Advanced => assets.advanced_asteroid.clone(),
-            AsteroidKind::SmallBasic => assets.small_basic_asteroid.clone(),
-            AsteroidKind::SmallAdvanced => assets.small_advanced_asteroid.clone(),
-        }
+        format!("{size}{kind}")
     }
 
-    fn get_collider_radius(&self) -> f32 {
-        match self {
-            AsteroidKind::Basic | AsteroidKind::Advanced => 28.,
-            AsteroidKind::SmallBasic | AsteroidKind::SmallAdvanced => 14.,
-        }
-    }
-
-    fn is_smaller(&self) -> bool {
+    pub fn get_health(&self) -> u16 {
         match self {
-            AsteroidKind::Basic | AsteroidKind::Advanced => false,
-            AsteroidKind::SmallBasic | AsteroidKind::SmallAdvanced => true,
+            AsteroidKind::Basic | AsteroidKind::Volatile => 1,
+            AsteroidKind::Magnetic => 2,
+            AsteroidKind::Advanced | AsteroidKind::Armoured => 3,
         }
     }
 
-    fn get_smaller(&self) -> Self {
+    fn get_color(&self) -> Color {
         match self {
-            AsteroidKind::Basic | AsteroidKind::SmallBasic => AsteroidKind::SmallBasic,
-            AsteroidKind::Advanced | AsteroidKind::SmallAdvanced => AsteroidKind::SmallAdvanced,
+            AsteroidKind::Basic => DARK_GRAY.into(),
+            AsteroidKind::Advanced => INDIAN_RED.into(),
+            AsteroidKind::Volatile => DARK_ORANGE.into(),
+            AsteroidKind::Magnetic => STEEL_BLUE.into(),
+            AsteroidKind::Armoured => SLATE_GRAY.into(),
         }
     }
 }
@@ -138,52 +448,143 @@ pub struct SpawnAsteroid {
     transform: Transform,
     direction: Vec3,
     id: usize,
+    tier: u8,
+    seed: u64,
+    velocity: Vec2,
+    shape: Option<AsteroidShape>,
 }
 
 impl SpawnAsteroid {
-    fn new(kind: AsteroidKind, transform: Transform, direction: Vec3, id: usize) -> Self {
+    pub fn new(
+        kind: AsteroidKind,
+        transform: Transform,
+        direction: Vec3,
+        id: usize,
+        tier: u8,
+        seed: u64,
+    ) -> Self {
         Self {
             kind,
             transform,
             direction,
             id,
+            tier,
+            seed,
+            velocity: Vec2::ZERO,
+            shape: None,
         }
     }
+
+    /// Spawn the asteroid already moving,
+    /// rather than accelerating from standing still.
+    fn with_velocity(mut self, velocity: Vec2) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
+    /// Spawn the asteroid with the given shape,
+    /// rather than generating a new one.
+    fn with_shape(mut self, shape: AsteroidShape) -> Self {
+        self.shape = Some(shape);
+        self
+    }
 }
 
 fn spawn_asteroid(
     trigger: Trigger<SpawnAsteroid>,
     mut commands: Commands,
-    assets: Res<AsteroidAssets>,
+    settings: AsteroidSettings,
+    collision_mode: Res<AsteroidCollisionMode>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
     let event = trigger.event();
+    let config = settings.get(event.kind);
+    let mut rng = seeded_stream(event.seed, Asteroid::SPAWN_STREAM);
+    let shape = event
+        .shape
+        .clone()
+        .unwrap_or_else(|| AsteroidShape::generate(&mut rng, config.radius));
 
-    commands.spawn((
-        Name::new(event.kind.get_name()),
+    let mut asteroid = commands.spawn((
+        Name::new(event.kind.get_name(event.tier)),
         event.kind,
         StateScoped(GameState::Playing),
         Asteroid {
             id: event.id,
             direction: event.direction,
+            tier: event.tier,
+            impact: Vec2::ZERO,
+            seed: event.seed,
         },
         DestroyOutOfBounds,
         Shootable,
+        Faction::Neutral,
         Health::new(event.kind.get_health()),
         Points(1),
-        SpriteBundle {
+        MaterialMesh2dBundle {
             transform: event.transform,
-            texture: event.kind.get_texture(assets),
+            mesh: Mesh2dHandle(meshes.add(shape.mesh())),
+            material: materials.add(event.kind.get_color()),
             ..default()
         },
-        RigidBody::Kinematic,
-        // TODO: Add a `#get_collider_radius()` method
-        // to AsteroidKind.
-        Collider::circle(event.kind.get_collider_radius()),
-        TranslationInterpolation,
-        RotationInterpolation,
+        shape.collider(),
+        shape,
+        (TranslationInterpolation, RotationInterpolation),
         LinearAcceleration(110.),
         AngularAcceleration(1.0),
     ));
+
+    if let Some(MagneticFieldConfig { radius, strength }) = config.magnetic_field {
+        asteroid.insert(MagneticField { radius, strength });
+    }
+
+    if let Some(ArmourConfig {
+        core_arc,
+        armoured_damage,
+    }) = config.armour
+    {
+        asteroid.insert(Armour {
+            core_direction: Vec2::from_angle(rng.gen_range(0.0..TAU)),
+            core_arc,
+            armoured_damage,
+        });
+    }
+
+    // Nothing steers dynamic asteroids, so they
+    // need to start off already up to speed.
+    let velocity = match *collision_mode {
+        AsteroidCollisionMode::Bounce if event.velocity == Vec2::ZERO => {
+            event.direction.xy() * ASTEROID_MAX_SPEED * settings.run_config.asteroid_speed_scale()
+        }
+        _ => event.velocity,
+    };
+    collision_mode.insert_body(&mut asteroid, velocity);
+
+    if *collision_mode == AsteroidCollisionMode::Bounce {
+        asteroid.insert(AngularVelocity(PI / 2.));
+    }
+}
+
+/// Convert the asteroids already in play when the [`AsteroidCollisionMode`]
+/// changes, so kinematic ones aren't left with nothing steering them.
+fn convert_asteroid_bodies(
+    query: Query<(Entity, &Asteroid, &LinearVelocity)>,
+    collision_mode: Res<AsteroidCollisionMode>,
+    run_config: Res<RunConfig>,
+    mut commands: Commands,
+) {
+    for (entity, asteroid, linear_velocity) in &query {
+        // Keep them moving as they were, but bring any that have
+        // stopped up to speed, as dynamic ones won't get there alone.
+        let velocity = if linear_velocity.0 == Vec2::ZERO {
+            asteroid.cruising_velocity(run_config.asteroid_speed_scale())
+        } else {
+            linear_velocity.0
+        };
+
+        collision_mode.insert_body(&mut commands.entity(entity), velocity);
+    }
 }
 
 fn move_asteroids(
@@ -195,8 +596,8 @@ fn move_asteroids(
         &mut AngularVelocity,
     )>,
     time: Res<Time<Fixed>>,
+    run_config: Res<RunConfig>,
 ) {
-    const LINEAR_MAX_SPEED: f32 = 200.;
     const ANGULAR_MAX_SPEED: f32 = PI;
 
     for (
@@ -207,7 +608,7 @@ fn move_asteroids(
         mut angular_velocity,
     ) in &mut query
     {
-        let target_velocity = (asteroid.direction * LINEAR_MAX_SPEED).xy();
+        let target_velocity = asteroid.cruising_velocity(run_config.asteroid_speed_scale());
 
         linear_velocity.0 = linear_velocity.0.move_towards(
             target_velocity,
@@ -223,69 +624,148 @@ fn move_asteroids(
 
 fn destroyed_asteroids(
     mut event_reader: EventReader<Destroyed>,
-    asteroid_query: Query<(&Health, &Points, &AsteroidKind, &Transform, &Asteroid)>,
-    mut score: ResMut<Score>,
-    mut small_asteroid_map: ResMut<SmallAsteroidMap>,
+    asteroid_query: Query<(
+        &Health,
+        &Points,
+        &AsteroidKind,
+        &Transform,
+        &LinearVelocity,
+        &Asteroid,
+        &AsteroidShape,
+    )>,
+    mut scoring: Scoring,
+    mut family_progress: FamilyProgress,
+    settings: AsteroidSettings,
+    mut drops: Drops,
     mut commands: Commands,
 ) {
-    let mut rng = thread_rng();
+    // Explosions make it likely that an asteroid is
+    // destroyed more than once in the same frame,
+    // which must only be counted the first time.
+    let mut handled = HashSet::new();
     for Destroyed(entity) in event_reader.read() {
-        if asteroid_query.contains(*entity) {
-            let (health, points, kind, transform, asteroid) = asteroid_query.get(*entity).unwrap();
-            if health.current() == 0 {
-                score.0 += points.0;
-                let spawn_health: bool = rng.gen();
-
-                if kind.is_smaller() && spawn_health {
-                    commands.trigger(SpawnHealthPickup::new(transform.translation));
-                } else if !kind.is_smaller() {
-                    for n in -1..=1 {
-                        const SMALL_ASTEROID_OFFSET: f32 = 90.;
-
-                        let mut new_direction = Transform::from_translation(asteroid.direction);
-                        new_direction.rotate_z(n as f32 * (PI / 8.));
-                        let new_direction = new_direction
-                            .rotation
-                            .mul_vec3(new_direction.translation)
-                            .normalize_or_zero();
-
-                        let mut new_transform = *transform;
-                        new_transform.translation += new_direction * SMALL_ASTEROID_OFFSET;
-
-                        commands.trigger(SpawnAsteroid::new(
-                            kind.get_smaller(),
+        if !handled.insert(*entity) {
+            continue;
+        }
+
+        let Ok((health, points, kind, transform, linear_velocity, asteroid, shape)) =
+            asteroid_query.get(*entity)
+        else {
+            continue;
+        };
+
+        let config = settings.get(*kind);
+        let mut pieces = 0;
+
+        if health.current() == 0 {
+            scoring.add(points);
+
+            if config.can_split(asteroid.tier) {
+                // How hard the projectile knocks the pieces along.
+                const IMPACT_SPEED: f32 = 60.;
+                // How fast the pieces drift apart from each other.
+                const SPREAD_SPEED: f32 = 40.;
+
+                // The pieces carry on with the parent's momentum,
+                // plus the push from the projectile that broke it.
+                let base_velocity = linear_velocity.0 + asteroid.impact * IMPACT_SPEED;
+                let base_direction = base_velocity
+                    .try_normalize()
+                    .map_or(asteroid.direction, |direction| direction.extend(0.0));
+
+                // Cut the parent's shape into pieces, starting the
+                // cuts from a random point on its rim, and place each
+                // piece where it was within the parent's footprint.
+                let mut rng = seeded_stream(asteroid.seed, Asteroid::SPLIT_STREAM);
+                let cut_start: usize = rng.gen();
+                let cut_pieces = shape.split(config.split_count as usize, cut_start);
+                let directions = config.get_split_directions(cut_pieces.len(), base_direction);
+
+                for ((piece, offset), new_direction) in cut_pieces.into_iter().zip(directions) {
+                    let offset = transform.rotation.mul_vec3(offset.extend(0.0));
+                    let new_velocity = Quat::from_rotation_arc(base_direction, new_direction)
+                        .mul_vec3(base_velocity.extend(0.0))
+                        .xy()
+                        + offset.xy().normalize_or_zero() * SPREAD_SPEED;
+
+                    let mut new_transform = *transform;
+                    new_transform.translation += offset;
+
+                    commands.trigger(
+                        SpawnAsteroid::new(
+                            *kind,
                             new_transform,
-                            new_direction,
+                            new_velocity.extend(0.0).normalize_or_zero(),
                             asteroid.id,
-                        ));
-                    }
+                            asteroid.tier + 1,
+                            rng.gen(),
+                        )
+                        .with_velocity(new_velocity)
+                        .with_shape(piece),
+                    );
+                    pieces += 1;
                 }
-            } else if !kind.is_smaller() {
-                commands.trigger(SpawnAsteroids::new(1));
             }
 
-            if kind.is_smaller() {
-                let value = small_asteroid_map.0.entry(asteroid.id).or_insert(0);
-                *value += 1;
-                if *value >= 3 {
-                    commands.trigger(SpawnAsteroids::new(1));
-                    small_asteroid_map.0.remove(&asteroid.id);
+            if let Some(effect) = drops
+                .roll(&config.drops, family_progress.wave.number())
+                .filter(|_| settings.run_config.pickups_enabled())
+            {
+                commands.trigger(SpawnPickup::new(transform.translation, effect));
+            }
+
+            for behaviour in &config.on_death {
+                match *behaviour {
+                    DeathBehaviour::Explode { radius, damage } => {
+                        // Zen runs make asteroids harmless, their explosions included.
+                        commands.trigger(
+                            Explode::new(transform.translation, radius, damage)
+                                .with_players_spared(settings.run_config.asteroids_harmless()),
+                        );
+                    }
                 }
             }
+        }
 
-            commands.entity(*entity).despawn_recursive();
+        if family_progress.destroy_member(asteroid.id, pieces) {
+            commands.trigger(SpawnAsteroids::new(1));
         }
+
+        commands.entity(*entity).despawn_recursive();
     }
 }
 
-fn shot_asteroids(
-    mut shot_event_reader: EventReader<Shot>,
+/// Damage asteroids that hit each other hard enough.
+///
+/// Only used with [`AsteroidCollisionMode::Bounce`],
+/// as kinematic asteroids pass straight through each other.
+fn asteroid_impacts(
+    mut collision_event_reader: EventReader<CollisionStarted>,
     mut destroyed_event_writer: EventWriter<Destroyed>,
-    mut asteroid_query: Query<&mut Health, With<Asteroid>>,
+    mut asteroid_query: Query<(&mut Health, &mut Asteroid, &LinearVelocity)>,
 ) {
-    for Shot(entity) in shot_event_reader.read() {
-        if asteroid_query.contains(*entity) {
-            let mut health = asteroid_query.get_mut(*entity).unwrap();
+    // Relative speed, after bouncing, above which
+    // both asteroids in a collision take damage.
+    const HARD_IMPACT_SPEED: f32 = 150.;
+
+    for CollisionStarted(entity1, entity2) in collision_event_reader.read() {
+        let Ok([(_, _, first_velocity), (_, _, second_velocity)]) =
+            asteroid_query.get_many([*entity1, *entity2])
+        else {
+            continue;
+        };
+
+        let relative_velocity = first_velocity.0 - second_velocity.0;
+        if relative_velocity.length() < HARD_IMPACT_SPEED {
+            continue;
+        }
+
+        for (entity, impact) in [(entity1, relative_velocity), (entity2, -relative_velocity)] {
+            let Ok((mut health, mut asteroid, _)) = asteroid_query.get_mut(*entity) else {
+                continue;
+            };
+
+            asteroid.impact = impact.normalize_or_zero();
             health.sub(1);
             if health.current() == 0 {
                 destroyed_event_writer.send(Destroyed(*entity));
@@ -293,3 +773,73 @@ fn shot_asteroids(
         }
     }
 }
+
+/// Use gizmos to render an arc around the
+/// exposed core of armoured asteroids.
+fn visualize_armour(mut gizmos: Gizmos, query: Query<(&Armour, &AsteroidShape, &Transform)>) {
+    const ARC_GAP: f32 = 4.;
+
+    for (armour, shape, transform) in &query {
+        let core_direction = (transform.rotation * armour.core_direction.extend(0.0)).xy();
+
+        gizmos.arc_2d(
+            transform.translation.xy(),
+            Vec2::Y.angle_between(core_direction),
+            armour.core_arc,
+            shape.get_radius() + ARC_GAP,
+            GOLD,
+        );
+    }
+}
+
+fn shot_asteroids(
+    mut shot_event_reader: EventReader<Shot>,
+    mut destroyed_event_writer: EventWriter<Destroyed>,
+    mut asteroid_query: Query<(&mut Health, &mut Asteroid, Option<&Armour>)>,
+) {
+    for shot in shot_event_reader.read() {
+        if asteroid_query.contains(shot.entity) {
+            let (mut health, mut asteroid, armour) = asteroid_query.get_mut(shot.entity).unwrap();
+            asteroid.impact = shot.direction;
+            health.sub(armour.map_or(1, |armour| armour.get_damage(shot.local_normal)));
+            if health.current() == 0 {
+                destroyed_event_writer.send(Destroyed(shot.entity));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn family_without_pieces_is_finished() {
+        let mut families = AsteroidFamilies::default();
+
+        assert!(families.destroy_member(0, 0));
+        assert!(families.0.is_empty());
+    }
+
+    #[test]
+    fn family_is_finished_once_every_piece_is_destroyed() {
+        let mut families = AsteroidFamilies::default();
+
+        // The parent splits in two, then one of its pieces in two again.
+        assert!(!families.destroy_member(0, 2));
+        assert!(!families.destroy_member(0, 2));
+        assert!(!families.destroy_member(0, 0));
+        assert!(!families.destroy_member(0, 0));
+        assert!(families.destroy_member(0, 0));
+        assert!(families.0.is_empty());
+    }
+
+    #[test]
+    fn families_are_counted_separately() {
+        let mut families = AsteroidFamilies::default();
+
+        assert!(!families.destroy_member(0, 2));
+        assert!(families.destroy_member(1, 0));
+        assert_eq!(families.0[&0].alive, 2);
+    }
+}
@@ -0,0 +1,161 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    Rng,
+};
+use serde::Deserialize;
+
+use crate::{pickup::PickupEffect, random::GameRng, states::GameState};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<DropPity>();
+    app.init_resource::<DropPity>();
+    app.add_systems(OnEnter(GameState::Playing), reset_drop_pity);
+}
+
+/// One possible outcome of a roll on a [`DropTable`].
+#[derive(Deserialize, Debug)]
+struct DropEntry {
+    /// How likely this entry is to be picked,
+    /// relative to the weights of the others.
+    weight: f32,
+    /// The pickup dropped, or `None` for nothing.
+    pickup: Option<PickupEffect>,
+}
+
+/// What an asteroid of a kind may leave behind when destroyed.
+#[derive(Deserialize, Debug, Default)]
+#[serde(transparent)]
+pub(super) struct DropTable(Vec<DropEntry>);
+
+impl DropTable {
+    /// Roll for a drop.
+    ///
+    /// The weights of the pickups are scaled up with the wave, so
+    /// drops get more common as things get harder, and after a long
+    /// enough dry streak, nothing is taken off the table entirely.
+    pub(super) fn roll(
+        &self,
+        rng: &mut impl Rng,
+        pity: &mut DropPity,
+        wave: u32,
+    ) -> Option<PickupEffect> {
+        // How many rolls in a row can come up empty
+        // before the next one is guaranteed a drop.
+        const PITY_THRESHOLD: u32 = 12;
+        // How much more likely drops are each wave.
+        const WAVE_SCALING: f32 = 0.15;
+        const MAX_SCALING: f32 = 2.5;
+
+        let scaling = (1. + wave.saturating_sub(1) as f32 * WAVE_SCALING).min(MAX_SCALING);
+        let guaranteed = pity.dry_streak >= PITY_THRESHOLD;
+
+        let weights = self.0.iter().map(|entry| match entry.pickup {
+            Some(_) => entry.weight.max(0.) * scaling,
+            None if guaranteed => 0.,
+            None => entry.weight.max(0.),
+        });
+
+        // An empty table, or one with nothing but
+        // empty entries left, can't drop anything.
+        let pickup = WeightedIndex::new(weights)
+            .ok()
+            .and_then(|index| self.0[index.sample(rng)].pickup);
+
+        if pickup.is_some() {
+            pity.dry_streak = 0;
+        } else {
+            pity.dry_streak += 1;
+        }
+
+        pickup
+    }
+}
+
+/// How many asteroids in a row have dropped nothing.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+pub(super) struct DropPity {
+    dry_streak: u32,
+}
+
+/// What drops are rolled with: the [`GameRng`]'s stream
+/// for them, and the pity carried over between rolls.
+#[derive(SystemParam)]
+pub(super) struct Drops<'w> {
+    game_rng: ResMut<'w, GameRng>,
+    pity: ResMut<'w, DropPity>,
+}
+
+impl Drops<'_> {
+    pub(super) fn roll(&mut self, table: &DropTable, wave: u32) -> Option<PickupEffect> {
+        table.roll(self.game_rng.drops(), &mut self.pity, wave)
+    }
+}
+
+fn reset_drop_pity(mut pity: ResMut<DropPity>) {
+    *pity = DropPity::default();
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    /// A table that all but never drops anything by chance.
+    fn stingy_table() -> DropTable {
+        DropTable(vec![
+            DropEntry {
+                weight: 1e9,
+                pickup: None,
+            },
+            DropEntry {
+                weight: 1e-9,
+                pickup: Some(PickupEffect::Heal(1)),
+            },
+        ])
+    }
+
+    #[test]
+    fn dry_streak_guarantees_a_drop() {
+        let table = stingy_table();
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut pity = DropPity::default();
+
+        for _ in 0..12 {
+            assert_eq!(table.roll(&mut rng, &mut pity, 1), None);
+        }
+        assert_eq!(pity.dry_streak, 12);
+
+        assert_eq!(table.roll(&mut rng, &mut pity, 1), Some(PickupEffect::Heal(1)));
+        assert_eq!(pity.dry_streak, 0);
+    }
+
+    #[test]
+    fn drop_resets_the_dry_streak() {
+        let table = DropTable(vec![DropEntry {
+            weight: 1.,
+            pickup: Some(PickupEffect::ExtraLife),
+        }]);
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut pity = DropPity { dry_streak: 5 };
+
+        assert_eq!(table.roll(&mut rng, &mut pity, 1), Some(PickupEffect::ExtraLife));
+        assert_eq!(pity.dry_streak, 0);
+    }
+
+    #[test]
+    fn pity_cannot_drop_from_an_empty_table() {
+        let table = DropTable(vec![DropEntry {
+            weight: 1.,
+            pickup: None,
+        }]);
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut pity = DropPity { dry_streak: 100 };
+
+        assert_eq!(table.roll(&mut rng, &mut pity, 1), None);
+        assert_eq!(table.roll(&mut rng, &mut pity, 1), None);
+        assert_eq!(pity.dry_streak, 102);
+    }
+}
@@ -0,0 +1,272 @@
+use std::f32::consts::TAU;
+
+use avian2d::prelude::*;
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+    },
+};
+use rand::Rng;
+
+/// The outline of an asteroid, as a polygon that
+/// is star shaped around its anchor point.
+///
+/// Every point of the rim can be seen from the anchor
+/// in a straight line, so the shape can be drawn as a fan
+/// of triangles from it, and cut into pieces along lines from it.
+#[derive(Component, Reflect, Debug, Clone)]
+#[reflect(Component)]
+pub struct AsteroidShape {
+    anchor: Vec2,
+    rim: Vec<Vec2>,
+    /// Whether the rim wraps around the anchor, or is
+    /// an open chain with the anchor on the outline
+    /// between its two ends, as for a cut off piece.
+    closed: bool,
+}
+
+impl AsteroidShape {
+    /// Generate a jagged rock with a radius of roughly `radius`.
+    pub fn generate(rng: &mut impl Rng, radius: f32) -> Self {
+        const MIN_VERTICES: usize = 10;
+        const MAX_VERTICES: usize = 14;
+        // How far, as a fraction of the radius, a
+        // vertex can be pulled in towards the centre.
+        const JAGGEDNESS: f32 = 0.35;
+        // How far, as a fraction of the gap between vertices,
+        // a vertex can be nudged around the centre.
+        const ANGLE_JITTER: f32 = 0.3;
+
+        let count = rng.gen_range(MIN_VERTICES..=MAX_VERTICES);
+        let rim = (0..count)
+            .map(|n| {
+                let angle =
+                    (n as f32 + rng.gen_range(-ANGLE_JITTER..=ANGLE_JITTER)) * TAU / count as f32;
+                let distance = radius * rng.gen_range(1.0 - JAGGEDNESS..=1.0);
+                Vec2::from_angle(angle) * distance
+            })
+            .collect();
+
+        Self {
+            anchor: Vec2::ZERO,
+            rim,
+            closed: true,
+        }
+    }
+
+    /// The number of rim edges, each making
+    /// up a triangle of the fan with the anchor.
+    fn edge_count(&self) -> usize {
+        if self.closed {
+            self.rim.len()
+        } else {
+            self.rim.len().saturating_sub(1)
+        }
+    }
+
+    fn triangles(&self) -> impl Iterator<Item = [Vec2; 3]> + '_ {
+        (0..self.edge_count()).map(|n| {
+            [
+                self.anchor,
+                self.rim[n],
+                self.rim[(n + 1) % self.rim.len()],
+            ]
+        })
+    }
+
+    /// The area weighted centre of the shape.
+    fn centroid(&self) -> Vec2 {
+        let (weighted_sum, area) =
+            self.triangles()
+                .fold((Vec2::ZERO, 0.0), |(weighted_sum, area), [a, b, c]| {
+                    let triangle_area = (b - a).perp_dot(c - a).abs() / 2.;
+                    (
+                        weighted_sum + (a + b + c) / 3. * triangle_area,
+                        area + triangle_area,
+                    )
+                });
+
+        if area > 0.0 {
+            weighted_sum / area
+        } else {
+            self.anchor
+        }
+    }
+
+    /// The points of the shape's outline, in order.
+    pub fn outline(&self) -> Vec<Vec2> {
+        if self.closed {
+            self.rim.clone()
+        } else {
+            std::iter::once(self.anchor)
+                .chain(self.rim.iter().copied())
+                .collect()
+        }
+    }
+
+    /// A rough radius of the shape, from its
+    /// origin to its furthest point.
+    pub fn get_radius(&self) -> f32 {
+        self.outline()
+            .iter()
+            .map(|point| point.length())
+            .fold(0.0, f32::max)
+    }
+
+    pub fn mesh(&self) -> Mesh {
+        let radius = self.get_radius().max(f32::EPSILON);
+
+        let positions: Vec<Vec2> = std::iter::once(self.anchor)
+            .chain(self.rim.iter().copied())
+            .collect();
+        let indices: Vec<u32> = (0..self.edge_count() as u32)
+            .flat_map(|n| [0, n + 1, (n + 1) % self.rim.len() as u32 + 1])
+            .collect();
+
+        Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+            .with_inserted_attribute(
+                Mesh::ATTRIBUTE_POSITION,
+                positions
+                    .iter()
+                    .map(|position| position.extend(0.0).to_array())
+                    .collect::<Vec<_>>(),
+            )
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; positions.len()])
+            .with_inserted_attribute(
+                Mesh::ATTRIBUTE_UV_0,
+                positions
+                    .iter()
+                    .map(|position| (*position / radius / 2. + 0.5).to_array())
+                    .collect::<Vec<_>>(),
+            )
+            .with_inserted_indices(Indices::U32(indices))
+    }
+
+    pub fn collider(&self) -> Collider {
+        let outline = self.outline();
+        let indices = (0..outline.len() as u32)
+            .map(|n| [n, (n + 1) % outline.len() as u32])
+            .collect();
+
+        Collider::convex_decomposition(outline, indices)
+    }
+
+    /// Cut the shape into up to `count` pieces, along lines from the anchor
+    /// to the rim, starting from the rim vertex at `start`.
+    ///
+    /// Each piece is re-centred around its own centroid, which is
+    /// returned alongside it as its offset from the original origin.
+    pub fn split(&self, count: usize, start: usize) -> Vec<(Self, Vec2)> {
+        let edge_count = self.edge_count();
+        let count = count.min(edge_count);
+        if count == 0 {
+            return vec![];
+        }
+
+        // Open chains must be cut from their start,
+        // otherwise the piece wrapping around the
+        // anchor wouldn't be star shaped any more.
+        let start = if self.closed { start % edge_count } else { 0 };
+
+        (0..count)
+            .map(|piece| {
+                let first_edge = start + piece * edge_count / count;
+                let last_edge = start + (piece + 1) * edge_count / count;
+
+                let rim = (first_edge..=last_edge)
+                    .map(|n| self.rim[n % self.rim.len()])
+                    .collect();
+
+                let piece = Self {
+                    anchor: self.anchor,
+                    rim,
+                    closed: false,
+                };
+
+                let centroid = piece.centroid();
+                (piece.translated(-centroid), centroid)
+            })
+            .collect()
+    }
+
+    fn translated(&self, offset: Vec2) -> Self {
+        Self {
+            anchor: self.anchor + offset,
+            rim: self.rim.iter().map(|point| *point + offset).collect(),
+            closed: self.closed,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn rock(seed: u64) -> AsteroidShape {
+        AsteroidShape::generate(&mut StdRng::seed_from_u64(seed), 40.)
+    }
+
+    fn area(shape: &AsteroidShape) -> f32 {
+        shape
+            .triangles()
+            .map(|[a, b, c]| (b - a).perp_dot(c - a).abs() / 2.)
+            .sum()
+    }
+
+    #[test]
+    fn splits_into_as_many_pieces_as_asked() {
+        let shape = rock(0);
+
+        assert_eq!(shape.split(3, 5).len(), 3);
+        assert_eq!(shape.split(0, 5).len(), 0);
+    }
+
+    #[test]
+    fn splits_into_no_more_pieces_than_edges() {
+        let shape = rock(0);
+
+        assert_eq!(shape.split(100, 0).len(), shape.edge_count());
+    }
+
+    #[test]
+    fn pieces_are_centred_on_their_centroids() {
+        let shape = rock(1);
+
+        for (piece, _) in shape.split(3, 2) {
+            assert!(piece.centroid().length() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn pieces_are_offset_to_where_they_were_in_the_parent() {
+        let shape = rock(2);
+        let pieces = shape.split(3, 7);
+
+        // Weighted by area, the pieces' offsets
+        // average out to the parent's own centroid.
+        let total_area: f32 = pieces.iter().map(|(piece, _)| area(piece)).sum();
+        let weighted_sum: Vec2 = pieces
+            .iter()
+            .map(|(piece, offset)| *offset * area(piece))
+            .sum();
+
+        assert!((total_area - area(&shape)).abs() < 1e-2);
+        assert!((weighted_sum / total_area - shape.centroid()).length() < 1e-3);
+    }
+
+    #[test]
+    fn pieces_split_twice_are_not_degenerate() {
+        for seed in 0..20 {
+            for (piece, _) in rock(seed).split(3, seed as usize) {
+                for (piece, _) in piece.split(2, 0) {
+                    assert!(piece.edge_count() > 0);
+                    assert!(area(&piece) > 1.);
+                }
+            }
+        }
+    }
+}
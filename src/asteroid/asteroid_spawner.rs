@@ -1,13 +1,30 @@
-use bevy::prelude::*;
-use rand::{seq::IteratorRandom, thread_rng, Rng};
+use avian2d::prelude::*;
+use bevy::{color::palettes::css::ORANGE_RED, ecs::system::SystemParam, prelude::*};
+use rand::{
+    distributions::{Distribution, WeightedIndex},
+    rngs::StdRng,
+    seq::SliceRandom,
+    Rng,
+};
 
-use crate::{states::GameState, BOTTOM_VIEWPORT_EDGE, LEFT_VIEWPORT_EDGE, RIGHT_VIEWPORT_EDGE, TOP_VIEWPORT_EDGE};
+use crate::{enemy::mine::SpawnMine, player::Player, random::GameRng, states::GameState, BOTTOM_VIEWPORT_EDGE, LEFT_VIEWPORT_EDGE, RIGHT_VIEWPORT_EDGE, TOP_VIEWPORT_EDGE};
 
-use super::{AsteroidID, AsteroidKind, SpawnAsteroid};
+use super::{AsteroidID, AsteroidKind, AsteroidSettings, SpawnAsteroid, ASTEROID_MAX_SPEED};
 
 pub(super) fn plugin(app: &mut App) {
+    app.register_type::<SpawnWarning>();
+    app.register_type::<SpawnersEnabled>();
+    app.init_resource::<SpawnersEnabled>();
+    app.add_systems(OnExit(GameState::Playing), reset_spawners_enabled);
     app.observe(setup_asteroid_spawners);
     app.observe(spawn_asteroids);
+    app.observe(spawn_mines);
+    app.add_systems(
+        Update,
+        (spawn_warning_tick, visualize_spawn_warnings)
+            .chain()
+            .run_if(in_state(GameState::Playing)),
+    );
 }
 
 #[derive(Component, Reflect, Clone)]
@@ -22,6 +39,64 @@ impl AsteroidSpawner {
             normal_direction: direction,
         }
     }
+
+    /// How close something leaving this spawner at `speed`
+    /// would come to the player within the safe zone window.
+    fn closest_approach(
+        &self,
+        position: Vec2,
+        speed: f32,
+        player_position: Vec2,
+        player_velocity: Vec2,
+    ) -> f32 {
+        closest_approach(
+            position,
+            self.normal_direction.xy() * speed,
+            player_position,
+            player_velocity,
+        )
+    }
+}
+
+/// How close something at `position` moving at `velocity`
+/// would come to the player within the safe zone window.
+///
+/// Both are assumed to keep travelling in a straight line.
+/// For asteroids, which accelerate up to their max speed from
+/// standing still, passing in that speed gives the worst case.
+fn closest_approach(
+    position: Vec2,
+    velocity: Vec2,
+    player_position: Vec2,
+    player_velocity: Vec2,
+) -> f32 {
+    let relative_position = player_position - position;
+    let relative_velocity = velocity - player_velocity;
+
+    let time = if relative_velocity.length_squared() > 0.0 {
+        (relative_position.dot(relative_velocity) / relative_velocity.length_squared())
+            .clamp(0.0, SAFE_ZONE_SECS)
+    } else {
+        0.0
+    };
+
+    (relative_velocity * time - relative_position).length()
+}
+
+/// The radius around the player that newly spawned
+/// asteroids must not start in, or pass through.
+const SAFE_ZONE_RADIUS: f32 = 160.;
+/// How far ahead, in seconds, a spawner's path
+/// is checked against the player's position.
+const SAFE_ZONE_SECS: f32 = 3.;
+
+/// A warning marker on the viewport edge
+/// where an asteroid is about to come in.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SpawnWarning {
+    timer: Timer,
+    direction: Vec2,
 }
 
 #[derive(Bundle)]
@@ -55,10 +130,48 @@ impl AsteroidSpawnerBundle {
     }
 }
 
+/// Whether the spawners send in asteroids, and enemies,
+/// by themselves; on at the start of every round.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct SpawnersEnabled(pub bool);
+
+impl Default for SpawnersEnabled {
+    fn default() -> Self {
+        Self(true)
+    }
+}
+
+pub fn spawners_enabled(spawners_enabled: Res<SpawnersEnabled>) -> bool {
+    spawners_enabled.0
+}
+
+fn reset_spawners_enabled(mut spawners_enabled: ResMut<SpawnersEnabled>) {
+    *spawners_enabled = SpawnersEnabled::default();
+}
+
+/// What the spawners draw from, as long as they're enabled.
+#[derive(SystemParam)]
+struct Spawning<'w> {
+    spawners_enabled: Res<'w, SpawnersEnabled>,
+    game_rng: ResMut<'w, GameRng>,
+}
+
+impl Spawning<'_> {
+    /// The [`GameRng`], or `None` if the spawners are turned off.
+    fn rng(&mut self) -> Option<&mut StdRng> {
+        self.spawners_enabled.0.then_some(&mut **self.game_rng)
+    }
+}
+
 #[derive(Event, Debug)]
 pub struct SetupAsteroidSpawners;
 
-fn setup_asteroid_spawners(_: Trigger<SetupAsteroidSpawners>, mut commands: Commands) {
+fn setup_asteroid_spawners(
+    _: Trigger<SetupAsteroidSpawners>,
+    mut commands: Commands,
+    mut game_rng: ResMut<GameRng>,
+) {
     const OFFSET: f32 = 40.;
 
     const OFFSET_RIGHT_VIEWPORT_EDGE: f32 = RIGHT_VIEWPORT_EDGE + OFFSET;
@@ -99,8 +212,8 @@ fn setup_asteroid_spawners(_: Trigger<SetupAsteroidSpawners>, mut commands: Comm
         ),
     ];
 
+    let rng = &mut **game_rng;
     for spawner_point in spawner_points {
-        let mut rng = thread_rng();
         let target: Vec3 = Vec3::new(
             rng.gen_range(-320.0..=320.0),
             rng.gen_range(-130.0..=130.0),
@@ -131,31 +244,228 @@ impl SpawnAsteroids {
 fn spawn_asteroids(
     trigger: Trigger<SpawnAsteroids>,
     query: Query<(&AsteroidSpawner, &Transform)>,
+    player_query: Query<(&Transform, &LinearVelocity), With<Player>>,
     mut commands: Commands,
     mut asteroid_id: ResMut<AsteroidID>,
+    settings: AsteroidSettings,
+    mut spawning: Spawning,
 ) {
-    let mut spawned_asteroids: Vec<Vec3> = vec![];
-    let mut rng = rand::thread_rng();
+    let Some(rng) = spawning.rng() else {
+        return;
+    };
 
-    while spawned_asteroids.len() < trigger.event().amount as usize {
-        let (spawner, transform) = query.iter().choose(&mut rng).unwrap();
+    let kind_weights = WeightedIndex::new(
+        AsteroidKind::ALL
+            .iter()
+            .map(|kind| settings.get(*kind).spawn_weight),
+    )
+    .expect("at least one asteroid kind has a spawn weight");
 
-        if spawned_asteroids.contains(&transform.translation) {
-            continue;
+    // Rank the spawners by how close their asteroid would
+    // get to the nearest player, and reject any that would
+    // come within any player's safe zone.
+    let mut safe_spawners: Vec<(&AsteroidSpawner, &Transform)> = vec![];
+    let mut unsafe_spawners: Vec<(&AsteroidSpawner, &Transform, f32)> = vec![];
+    for (spawner, transform) in &query {
+        let position = transform.translation.xy();
+        let (approach, distance) = player_query.iter().fold(
+            (f32::INFINITY, f32::INFINITY),
+            |(approach, distance), (player_transform, player_velocity)| {
+                let player_position = player_transform.translation.xy();
+                (
+                    approach.min(spawner.closest_approach(
+                        position,
+                        ASTEROID_MAX_SPEED,
+                        player_position,
+                        player_velocity.0,
+                    )),
+                    distance.min(position.distance(player_position)),
+                )
+            },
+        );
+
+        if approach > SAFE_ZONE_RADIUS && distance > SAFE_ZONE_RADIUS {
+            safe_spawners.push((spawner, transform));
+        } else {
+            unsafe_spawners.push((spawner, transform, approach));
         }
+    }
+
+    let amount = trigger.event().amount as usize;
+    let mut chosen_spawners: Vec<(&AsteroidSpawner, &Transform)> = safe_spawners
+        .choose_multiple(rng, amount)
+        .copied()
+        .collect();
 
-        let spawn_basic: bool = rng.gen();
+    // If there are not enough safe spawners,
+    // fall back onto the least dangerous ones
+    // rather than dropping asteroids altogether.
+    if chosen_spawners.len() < amount {
+        unsafe_spawners.sort_by(|a, b| b.2.total_cmp(&a.2));
+        chosen_spawners.extend(
+            unsafe_spawners
+                .iter()
+                .take(amount - chosen_spawners.len())
+                .map(|(spawner, transform, _)| (*spawner, *transform)),
+        );
+    }
+
+    for (spawner, transform) in chosen_spawners {
         commands.trigger(SpawnAsteroid::new(
-            if spawn_basic {
-                AsteroidKind::Basic
-            } else {
-                AsteroidKind::Advanced
-            },
+            AsteroidKind::ALL[kind_weights.sample(rng)],
             *transform,
             spawner.normal_direction,
             asteroid_id.get(),
+            0,
+            rng.gen(),
+        ));
+
+        commands.spawn((
+            Name::new("SpawnWarning"),
+            StateScoped(GameState::Playing),
+            SpawnWarning {
+                timer: Timer::from_seconds(SPAWN_WARNING_SECS, TimerMode::Once),
+                direction: spawner.normal_direction.xy(),
+            },
+            TransformBundle::from_transform(Transform::from_translation(
+                transform.translation.clamp(
+                    Vec3::new(
+                        LEFT_VIEWPORT_EDGE + SPAWN_WARNING_MARGIN,
+                        BOTTOM_VIEWPORT_EDGE + SPAWN_WARNING_MARGIN,
+                        0.0,
+                    ),
+                    Vec3::new(
+                        RIGHT_VIEWPORT_EDGE - SPAWN_WARNING_MARGIN,
+                        TOP_VIEWPORT_EDGE - SPAWN_WARNING_MARGIN,
+                        0.0,
+                    ),
+                ),
+            )),
         ));
+    }
+}
+
+const SPAWN_WARNING_SECS: f32 = 1.5;
+const SPAWN_WARNING_MARGIN: f32 = 24.;
+
+fn spawn_warning_tick(
+    mut query: Query<(&mut SpawnWarning, Entity)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (mut warning, entity) in &mut query {
+        warning.timer.tick(time.delta());
+        if warning.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Use gizmos to render a blinking arrow on the
+/// viewport edge, pointing the way the incoming
+/// asteroid will travel.
+fn visualize_spawn_warnings(mut gizmos: Gizmos, query: Query<(&SpawnWarning, &Transform)>) {
+    const BLINKS_PER_SEC: f32 = 4.;
+    const ARROW_LENGTH: f32 = 32.;
+
+    for (warning, transform) in &query {
+        let blink = (warning.timer.elapsed_secs() * BLINKS_PER_SEC).fract() < 0.5;
+        if !blink {
+            continue;
+        }
+
+        let position = transform.translation.xy();
+        gizmos.arrow_2d(
+            position,
+            position + warning.direction * ARROW_LENGTH,
+            ORANGE_RED,
+        );
+    }
+}
 
-        spawned_asteroids.push(transform.translation);
+#[derive(Event, Debug)]
+pub struct SpawnMines {
+    amount: u16,
+}
+
+impl SpawnMines {
+    pub fn new(amount: u16) -> Self {
+        Self { amount }
+    }
+}
+
+/// Place mines either drifting in from a spawner, like an
+/// asteroid would, or sitting still somewhere in the play area.
+///
+/// Mines have their own safe zone, bigger than the asteroids' one,
+/// as they don't leave once they are placed. Neither the mine's
+/// drift, nor the player's own path, may bring them within it.
+fn spawn_mines(
+    trigger: Trigger<SpawnMines>,
+    spawner_query: Query<(&AsteroidSpawner, &Transform)>,
+    player_query: Query<(&Transform, &LinearVelocity), With<Player>>,
+    mut commands: Commands,
+    mut spawning: Spawning,
+) {
+    const MINE_DRIFT_SPEED: f32 = 30.;
+    const MINE_SAFE_ZONE_RADIUS: f32 = 240.;
+    // How far in from the viewport edges
+    // stationary mines are placed.
+    const FIELD_MARGIN: f32 = 60.;
+    // How many random points are tried for a
+    // stationary mine before giving up on it.
+    const PLACEMENT_ATTEMPTS: usize = 16;
+
+    let Some(rng) = spawning.rng() else {
+        return;
+    };
+
+    let is_safe = |position: Vec2, velocity: Vec2| {
+        player_query
+            .iter()
+            .all(|(player_transform, player_velocity)| {
+                closest_approach(
+                    position,
+                    velocity,
+                    player_transform.translation.xy(),
+                    player_velocity.0,
+                ) > MINE_SAFE_ZONE_RADIUS
+            })
+    };
+
+    for _ in 0..trigger.event().amount {
+        let drifting: bool = rng.gen();
+        if drifting {
+            let safe_spawners: Vec<(&AsteroidSpawner, &Transform)> = spawner_query
+                .iter()
+                .filter(|(spawner, transform)| {
+                    is_safe(
+                        transform.translation.xy(),
+                        spawner.normal_direction.xy() * MINE_DRIFT_SPEED,
+                    )
+                })
+                .collect();
+
+            if let Some((spawner, transform)) = safe_spawners.choose(rng) {
+                commands.trigger(
+                    SpawnMine::new(transform.translation)
+                        .with_velocity(spawner.normal_direction.xy() * MINE_DRIFT_SPEED),
+                );
+                continue;
+            }
+        }
+
+        let position = (0..PLACEMENT_ATTEMPTS)
+            .map(|_| {
+                Vec2::new(
+                    rng.gen_range(LEFT_VIEWPORT_EDGE + FIELD_MARGIN..=RIGHT_VIEWPORT_EDGE - FIELD_MARGIN),
+                    rng.gen_range(BOTTOM_VIEWPORT_EDGE + FIELD_MARGIN..=TOP_VIEWPORT_EDGE - FIELD_MARGIN),
+                )
+            })
+            .find(|position| is_safe(*position, Vec2::ZERO));
+
+        if let Some(position) = position {
+            commands.trigger(SpawnMine::new(position.extend(0.0)));
+        }
     }
 }
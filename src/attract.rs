@@ -0,0 +1,173 @@
+use bevy::{ecs::system::SystemParam, input::mouse::MouseMotion, prelude::*};
+
+use crate::{
+    autopilot::AutopilotEnabled, game_mode::GameMode, network::NetworkSession, round::RoundMode, run_config::RunConfig, states::{title::{setup_title, spawn_title_menu}, GameState}
+};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<AttractMode>();
+    app.init_resource::<AttractMode>();
+    // The menu shows the settings, so they need to be back first.
+    app.add_systems(OnEnter(GameState::Title), end_attract_mode.before(setup_title));
+    app.add_systems(
+        Update,
+        attract_idle_tick.run_if(in_state(GameState::Title)),
+    );
+    app.add_systems(
+        OnEnter(GameState::Playing),
+        spawn_attract_menu.run_if(is_attract_mode),
+    );
+    app.add_systems(
+        Update,
+        leave_attract_mode.run_if(
+            in_state(GameState::Playing)
+                .or_else(in_state(GameState::GameOver))
+                .and_then(is_attract_mode),
+        ),
+    );
+}
+
+pub fn is_attract_mode(attract_mode: Res<AttractMode>) -> bool {
+    attract_mode.active
+}
+
+/// A demo round, flown by the autopilot, that plays behind
+/// the title menu once it's been left alone for a while,
+/// the way arcade machines draw people in.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct AttractMode {
+    active: bool,
+    /// How long the title screen has been left alone.
+    idle: Timer,
+    /// How long the demo round has been going.
+    demo: Timer,
+    /// The player's own settings, put back after the demo.
+    saved: Option<(GameMode, RoundMode, RunConfig, bool)>,
+}
+
+impl Default for AttractMode {
+    fn default() -> Self {
+        Self {
+            active: false,
+            idle: Timer::from_seconds(Self::IDLE_SECS, TimerMode::Once),
+            demo: Timer::from_seconds(Self::DEMO_SECS, TimerMode::Once),
+            saved: None,
+        }
+    }
+}
+
+impl AttractMode {
+    const IDLE_SECS: f32 = 20.;
+    const DEMO_SECS: f32 = 45.;
+}
+
+/// Every key, mouse and gamepad button, and the mouse's motion.
+#[derive(SystemParam)]
+struct AnyInput<'w, 's> {
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse_buttons: Res<'w, ButtonInput<MouseButton>>,
+    gamepad_buttons: Res<'w, ButtonInput<GamepadButton>>,
+    mouse_motion: EventReader<'w, 's, MouseMotion>,
+}
+
+impl AnyInput<'_, '_> {
+    /// Whether any key, mouse or gamepad button was just pressed,
+    /// or the mouse moved.
+    fn just_happened(&mut self) -> bool {
+        // Read every motion event, so none are left over for next time.
+        let mouse_moved = self.mouse_motion.read().count() > 0;
+
+        mouse_moved
+            || self.keys.get_just_pressed().next().is_some()
+            || self.mouse_buttons.get_just_pressed().next().is_some()
+            || self.gamepad_buttons.get_just_pressed().next().is_some()
+    }
+}
+
+/// The player's own settings, which the demo swaps out.
+#[derive(SystemParam)]
+struct PlayerSettings<'w> {
+    game_mode: ResMut<'w, GameMode>,
+    round_mode: ResMut<'w, RoundMode>,
+    run_config: ResMut<'w, RunConfig>,
+    autopilot_enabled: ResMut<'w, AutopilotEnabled>,
+}
+
+impl PlayerSettings<'_> {
+    fn get(&self) -> (GameMode, RoundMode, RunConfig, bool) {
+        (*self.game_mode, *self.round_mode, *self.run_config, self.autopilot_enabled.0)
+    }
+
+    fn set(
+        &mut self,
+        (game_mode, round_mode, run_config, autopilot_enabled): (GameMode, RoundMode, RunConfig, bool),
+    ) {
+        *self.game_mode = game_mode;
+        *self.round_mode = round_mode;
+        *self.run_config = run_config;
+        self.autopilot_enabled.0 = autopilot_enabled;
+    }
+}
+
+/// Coming back to the title screen, put the player's own settings
+/// back if it was from a demo, and start waiting all over again.
+fn end_attract_mode(mut attract_mode: ResMut<AttractMode>, mut settings: PlayerSettings) {
+    if let Some(saved) = attract_mode.saved.take() {
+        settings.set(saved);
+    }
+
+    *attract_mode = AttractMode::default();
+}
+
+fn attract_idle_tick(
+    mut attract_mode: ResMut<AttractMode>,
+    time: Res<Time>,
+    mut input: AnyInput,
+    mut settings: PlayerSettings,
+    network_session: Option<Res<NetworkSession>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if input.just_happened() {
+        attract_mode.idle.reset();
+        return;
+    }
+
+    // An online session is waiting on its peers, not idle.
+    if network_session.is_some() || !attract_mode.idle.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    // The demo is always a plain solo round.
+    attract_mode.saved = Some(settings.get());
+    attract_mode.active = true;
+    settings.set((GameMode::Solo, RoundMode::Classic, RunConfig::default(), true));
+    next_state.set(GameState::Playing);
+}
+
+/// Any input, the demo running out, or the
+/// autopilot losing, goes back to the title screen.
+fn leave_attract_mode(
+    mut attract_mode: ResMut<AttractMode>,
+    time: Res<Time<Real>>,
+    state: Res<State<GameState>>,
+    mut input: AnyInput,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let input = input.just_happened();
+    let demo_over = attract_mode.demo.tick(time.delta()).finished();
+
+    if input || demo_over || *state.get() == GameState::GameOver {
+        next_state.set(GameState::Title);
+    }
+}
+
+/// Keep the menu up over the demo, showing the player's own
+/// settings, so the demo plays out behind it.
+fn spawn_attract_menu(mut commands: Commands, attract_mode: Res<AttractMode>) {
+    let Some((_, round_mode, run_config, _)) = attract_mode.saved else {
+        return;
+    };
+
+    spawn_title_menu(&mut commands, round_mode, run_config, GameState::Playing);
+}
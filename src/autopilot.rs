@@ -0,0 +1,201 @@
+//! A built-in pilot that flies a ship in place of the keyboard,
+//! for the title screen's demo rounds and for soak testing.
+//!
+//! Every local ship can be handed to it from the command line:
+//!
+//! ```text
+//! game_binary --autopilot
+//! game_binary --soak 1000 --headless
+//! ```
+//!
+//! A soak test plays the given number of rounds back to back,
+//! then exits; any panic along the way ends it early, with
+//! a non-zero exit code. `--headless` runs it without a window.
+//! A round the autopilot survives for longer than `--soak-max-secs`,
+//! 600 by default, is cut short and reported as a timeout.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+use crate::{
+    asteroid::Asteroid, pickup::Pickup, player::Player, projectile::PROJECTILE_SPEED, states::GameState, Action
+};
+
+pub mod soak;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Autopilot>();
+    app.register_type::<AutopilotEnabled>();
+    app.insert_resource(AutopilotEnabled(
+        std::env::args().any(|arg| arg == "--autopilot"),
+    ));
+    app.add_systems(
+        PreUpdate,
+        drive_autopilot
+            .in_set(InputManagerSystem::ManualControl)
+            .run_if(in_state(GameState::Playing)),
+    );
+    app.add_plugins(soak::plugin);
+}
+
+/// Whether newly spawned local ships are flown by
+/// the [`Autopilot`], rather than from the keyboard.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+pub struct AutopilotEnabled(pub bool);
+
+/// Flies a ship by writing its [`ActionState<Action>`],
+/// the same way the keyboard would.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct Autopilot;
+
+/// How an asteroid is moving relative to the ship.
+struct Approach {
+    /// The asteroid's position and velocity.
+    position: Vec2,
+    velocity: Vec2,
+    /// How soon, in seconds, the asteroid comes closest.
+    secs: f32,
+    /// Where the asteroid will be, relative to the ship, when it does.
+    offset: Vec2,
+}
+
+impl Approach {
+    /// How far ahead asteroids are followed.
+    const LOOKAHEAD_SECS: f32 = 2.;
+    /// How close an asteroid has to pass, from the ship's
+    /// centre, to count as being on a collision course.
+    const DANGER_RADIUS: f32 = 90.;
+
+    fn new(position: Vec2, velocity: Vec2, ship_position: Vec2, ship_velocity: Vec2) -> Self {
+        let relative_position = position - ship_position;
+        let relative_velocity = velocity - ship_velocity;
+
+        let speed_squared = relative_velocity.length_squared();
+        let secs = if speed_squared > 0. {
+            (-relative_position.dot(relative_velocity) / speed_squared)
+                .clamp(0., Self::LOOKAHEAD_SECS)
+        } else {
+            0.
+        };
+
+        Self {
+            position,
+            velocity,
+            secs,
+            offset: relative_position + relative_velocity * secs,
+        }
+    }
+
+    fn is_threat(&self) -> bool {
+        self.offset.length() < Self::DANGER_RADIUS
+    }
+}
+
+/// The rotate input that turns `heading` towards `direction`.
+fn steer(heading: Vec2, direction: Vec2) -> f32 {
+    // Positive rotate input turns right, which is clockwise.
+    (direction.angle_between(heading) * 2.).clamp(-1., 1.)
+}
+
+/// Dodge whatever is about to hit the ship, then shoot whatever
+/// is on course to, then pick up anything lying around, and
+/// otherwise shoot the nearest asteroid.
+///
+/// Targets are led by how long a shot would take to reach them,
+/// and only fired at once the ship is lined up with them.
+#[allow(clippy::type_complexity)]
+fn drive_autopilot(
+    mut pilot_query: Query<
+        (&mut ActionState<Action>, &Transform, &LinearVelocity),
+        (With<Autopilot>, With<Player>),
+    >,
+    asteroid_query: Query<(&Transform, &LinearVelocity), With<Asteroid>>,
+    pickup_query: Query<&Transform, With<Pickup>>,
+) {
+    // How far off, in radians, the aim can be and still fire.
+    const AIM_TOLERANCE: f32 = 0.15;
+    // How soon a collision has to be to dodge it, rather than shoot it.
+    const EVADE_SECS: f32 = 0.6;
+    // How far away the nearest asteroid has to be before the ship closes in.
+    const APPROACH_DISTANCE: f32 = 350.;
+
+    for (mut action_state, transform, ship_velocity) in &mut pilot_query {
+        let position = transform.translation.xy();
+        let heading = (transform.rotation * Vec3::Y).xy();
+
+        let approaches: Vec<Approach> = asteroid_query
+            .iter()
+            .map(|(asteroid_transform, velocity)| {
+                Approach::new(
+                    asteroid_transform.translation.xy(),
+                    velocity.0,
+                    position,
+                    ship_velocity.0,
+                )
+            })
+            .collect();
+
+        let threat = approaches
+            .iter()
+            .filter(|approach| approach.is_threat())
+            .min_by(|a, b| a.secs.total_cmp(&b.secs));
+        let nearest = approaches.iter().min_by(|a, b| {
+            a.position
+                .distance_squared(position)
+                .total_cmp(&b.position.distance_squared(position))
+        });
+        let pickup = pickup_query
+            .iter()
+            .map(|pickup_transform| pickup_transform.translation.xy())
+            .min_by(|a, b| a.distance_squared(position).total_cmp(&b.distance_squared(position)));
+
+        // Where each asteroid has to be shot at to hit it.
+        let lead = |approach: &Approach| {
+            let travel_secs = approach.position.distance(position) / PROJECTILE_SPEED;
+            (approach.position + approach.velocity * travel_secs - position).normalize_or_zero()
+        };
+
+        let (direction, thrust) = match (threat, pickup, nearest) {
+            // Turn away from where the asteroid will
+            // be, and get out of its path.
+            (Some(threat), ..) if threat.secs < EVADE_SECS => {
+                let away = -threat.offset.normalize_or(-threat.velocity.perp());
+                (away, away.angle_between(heading).abs() < AIM_TOLERANCE * 4.)
+            }
+            (Some(threat), ..) => (lead(threat), false),
+            (None, Some(pickup), _) => {
+                let towards = (pickup - position).normalize_or_zero();
+                (towards, towards.angle_between(heading).abs() < AIM_TOLERANCE)
+            }
+            (None, None, Some(nearest)) => {
+                let aim = lead(nearest);
+                let far = nearest.position.distance(position) > APPROACH_DISTANCE;
+                (aim, far && aim.angle_between(heading).abs() < AIM_TOLERANCE)
+            }
+            (None, None, None) => (heading, false),
+        };
+
+        action_state.set_value(&Action::Rotate, steer(heading, direction));
+
+        if thrust {
+            action_state.press(&Action::Move);
+        } else {
+            action_state.release(&Action::Move);
+        }
+
+        // Fire at anything the ship happens to be lined up with,
+        // even while dodging or heading for a pickup. Shooting only
+        // fires when first pressed, so let go in between.
+        let lined_up = approaches
+            .iter()
+            .any(|approach| lead(approach).angle_between(heading).abs() < AIM_TOLERANCE);
+        if lined_up && !action_state.pressed(&Action::Shoot) {
+            action_state.press(&Action::Shoot);
+        } else {
+            action_state.release(&Action::Shoot);
+        }
+    }
+}
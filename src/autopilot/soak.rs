@@ -0,0 +1,177 @@
+use bevy::prelude::*;
+
+use crate::{
+    game_mode::GameMode, round::{RoundClock, RoundMode}, run_config::RunConfig, states::GameState, stats::Score
+};
+
+use super::AutopilotEnabled;
+
+pub(super) fn plugin(app: &mut App) {
+    let Some(soak) = Soak::from_args(std::env::args().skip(1)) else {
+        return;
+    };
+
+    app.insert_resource(soak);
+    app.add_systems(OnEnter(GameState::Title), start_soak_round);
+    app.add_systems(
+        Update,
+        soak_round_timeout.run_if(in_state(GameState::Playing)),
+    );
+    app.add_systems(OnEnter(GameState::GameOver), finish_soak_round);
+}
+
+/// Rounds played back to back by the autopilot,
+/// as a test for anything that goes wrong over time.
+#[derive(Resource, Debug)]
+struct Soak {
+    rounds: u32,
+    /// How long a round can go on for before it's cut short,
+    /// as classic rounds only end when the ship is destroyed.
+    max_round_secs: f32,
+    rounds_played: u32,
+    /// How many rounds were cut short, rather than lost.
+    timeouts: u32,
+    /// Whether the current round was cut short.
+    timed_out: bool,
+}
+
+impl Soak {
+    const DEFAULT_MAX_ROUND_SECS: f32 = 600.;
+
+    /// Returns `None` if no soak test was asked
+    /// for, or it was asked for incorrectly.
+    fn from_args(mut args: impl Iterator<Item = String>) -> Option<Self> {
+        let mut rounds = None;
+        let mut max_round_secs = Self::DEFAULT_MAX_ROUND_SECS;
+
+        while let Some(arg) = args.next() {
+            if arg != "--soak" && arg != "--soak-max-secs" {
+                continue;
+            }
+
+            let value = args.next();
+            let parsed = match arg.as_str() {
+                "--soak" => value
+                    .as_deref()
+                    .and_then(|value| value.parse().ok())
+                    .map(|count| rounds = Some(count)),
+                _ => value
+                    .as_deref()
+                    .and_then(|value| value.parse().ok())
+                    .filter(|secs: &f32| *secs > 0.)
+                    .map(|secs| max_round_secs = secs),
+            };
+
+            if parsed.is_none() {
+                error!("Couldn't use {value:?} for {arg}");
+                return None;
+            }
+        }
+
+        Some(Self {
+            rounds: rounds?,
+            max_round_secs,
+            rounds_played: 0,
+            timeouts: 0,
+            timed_out: false,
+        })
+    }
+}
+
+/// Skip the title screen, straight into a plain solo
+/// round, with the autopilot flying the ship.
+fn start_soak_round(
+    mut game_mode: ResMut<GameMode>,
+    mut round_mode: ResMut<RoundMode>,
+    mut run_config: ResMut<RunConfig>,
+    mut autopilot_enabled: ResMut<AutopilotEnabled>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    *game_mode = GameMode::Solo;
+    *round_mode = RoundMode::Classic;
+    *run_config = RunConfig::default();
+    autopilot_enabled.0 = true;
+    next_state.set(GameState::Playing);
+}
+
+/// End a round that the autopilot has survived for too long,
+/// so that a good enough pilot can't stall the whole test.
+fn soak_round_timeout(
+    mut soak: ResMut<Soak>,
+    clock: Res<RoundClock>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !soak.timed_out && clock.elapsed_secs() >= soak.max_round_secs {
+        soak.timed_out = true;
+        next_state.set(GameState::GameOver);
+    }
+}
+
+fn finish_soak_round(
+    mut soak: ResMut<Soak>,
+    score: Res<Score>,
+    clock: Res<RoundClock>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut exit_event_writer: EventWriter<AppExit>,
+) {
+    soak.rounds_played += 1;
+    let outcome = if soak.timed_out {
+        soak.timeouts += 1;
+        "timed out"
+    } else {
+        "over"
+    };
+    soak.timed_out = false;
+
+    info!(
+        "Soak round {}/{} {outcome}, with a score of {} after {:.1}s",
+        soak.rounds_played,
+        soak.rounds,
+        score.0,
+        clock.elapsed_secs()
+    );
+
+    if soak.rounds_played >= soak.rounds {
+        info!(
+            "Soak test finished, {} of {} rounds timed out",
+            soak.timeouts, soak.rounds_played
+        );
+        exit_event_writer.send(AppExit::Success);
+    } else {
+        next_state.set(GameState::Playing);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> impl Iterator<Item = String> {
+        args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>().into_iter()
+    }
+
+    #[test]
+    fn rounds_are_capped_by_default() {
+        let soak = Soak::from_args(args(&["--headless", "--soak", "20"])).unwrap();
+
+        assert_eq!(soak.rounds, 20);
+        assert_eq!(soak.max_round_secs, Soak::DEFAULT_MAX_ROUND_SECS);
+    }
+
+    #[test]
+    fn max_round_secs_can_be_set() {
+        let soak = Soak::from_args(args(&["--soak-max-secs", "90", "--soak", "5"])).unwrap();
+
+        assert_eq!(soak.rounds, 5);
+        assert_eq!(soak.max_round_secs, 90.);
+    }
+
+    #[test]
+    fn bad_values_are_rejected() {
+        assert!(Soak::from_args(args(&[])).is_none());
+        assert!(Soak::from_args(args(&["--soak-max-secs", "90"])).is_none());
+        assert!(Soak::from_args(args(&["--soak", "many"])).is_none());
+        assert!(Soak::from_args(args(&["--soak", "5", "--soak-max-secs", "0"])).is_none());
+        assert!(Soak::from_args(args(&["--soak", "5", "--soak-max-secs"])).is_none());
+    }
+}
@@ -0,0 +1,218 @@
+use std::{
+    collections::BTreeMap, fs, io, time::{SystemTime, UNIX_EPOCH}
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    autopilot::AutopilotEnabled, game_mode::GameMode, leaderboard::LeaderboardEntry, random::{reseed_game_rng, GameRng}, round::{RoundClock, RoundMode}, run_config::RunConfig, states::GameState, stats::Score
+};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<DailyChallenge>();
+    app.add_systems(Startup, load_daily_results);
+    app.add_systems(
+        OnEnter(GameState::Playing),
+        start_daily_challenge
+            .after(reseed_game_rng)
+            .run_if(is_daily_challenge),
+    );
+    app.add_systems(
+        OnExit(GameState::Playing),
+        record_daily_result.run_if(is_daily_challenge),
+    );
+}
+
+fn is_daily_challenge(round_mode: Res<RoundMode>, game_mode: Res<GameMode>) -> bool {
+    *round_mode == RoundMode::DailyChallenge && !game_mode.is_versus()
+}
+
+/// Where the daily results are kept, next to wherever the game is run from.
+const DAILY_RESULTS_PATH: &str = "daily.ron";
+
+/// A day, counted in whole days since the Unix epoch, in UTC,
+/// so everyone playing on the same day gets the same challenge.
+#[derive(
+    Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord,
+)]
+#[serde(transparent)]
+pub struct Day(pub u32);
+
+impl Day {
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs());
+        Self((secs / 86_400) as u32)
+    }
+
+    /// The seed of this day's challenge, scrambled
+    /// so that neighbouring days play differently.
+    pub fn seed(&self) -> u64 {
+        // SplitMix64, which is fully specified, so the seed
+        // doesn't depend on the platform or the Rust version.
+        let mut z = (self.0 as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// The day as a year, month and day of the month.
+    pub fn to_date(&self) -> (i32, u32, u32) {
+        // Howard Hinnant's `civil_from_days`.
+        let z = self.0 as i64 + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        } as u32;
+        let year = (year_of_era + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+
+        (year, month, day)
+    }
+}
+
+impl std::fmt::Display for Day {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (year, month, day) = self.to_date();
+        write!(f, "{year}-{month:02}-{day:02}")
+    }
+}
+
+/// The daily challenge being played, or last played.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct DailyChallenge {
+    pub day: Day,
+    pub seed: u64,
+    /// Only the first ranked solo attempt of a day is
+    /// scored, any after that, or played any other way,
+    /// are just practice.
+    pub scored: bool,
+}
+
+/// The scored result of every daily challenge played.
+#[derive(Resource, Serialize, Deserialize, Debug, Default)]
+pub struct DailyResults(BTreeMap<Day, LeaderboardEntry>);
+
+impl DailyResults {
+    pub fn get(&self, day: Day) -> Option<&LeaderboardEntry> {
+        self.0.get(&day)
+    }
+
+    fn load() -> io::Result<Self> {
+        let contents = fs::read_to_string(DAILY_RESULTS_PATH)?;
+        ron::from_str(&contents).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let contents = ron::ser::to_string_pretty(self, default())
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(DAILY_RESULTS_PATH, contents)
+    }
+}
+
+fn load_daily_results(mut commands: Commands) {
+    let results = match DailyResults::load() {
+        Ok(results) => results,
+        // No results yet is just a first run.
+        Err(error) if error.kind() == io::ErrorKind::NotFound => default(),
+        Err(error) => {
+            warn!("Couldn't load the daily results: {error}");
+            default()
+        }
+    };
+    commands.insert_resource(results);
+}
+
+/// Seed the round from today's date, in place of the fresh seed it got.
+///
+/// Whether the attempt is scored is settled here, the same
+/// way the leaderboards decide what to rank: co-op scores are
+/// shared, and neither zen nor the autopilot are real attempts.
+fn start_daily_challenge(
+    mut commands: Commands,
+    mut game_rng: ResMut<GameRng>,
+    results: Res<DailyResults>,
+    game_mode: Res<GameMode>,
+    run_config: Res<RunConfig>,
+    autopilot_enabled: Res<AutopilotEnabled>,
+) {
+    let day = Day::today();
+    let seed = day.seed();
+    *game_rng = GameRng::new(seed);
+
+    commands.insert_resource(DailyChallenge {
+        day,
+        seed,
+        scored: *game_mode == GameMode::Solo
+            && run_config.is_ranked()
+            && !autopilot_enabled.0
+            && results.get(day).is_none(),
+    });
+}
+
+fn record_daily_result(
+    challenge: Res<DailyChallenge>,
+    mut results: ResMut<DailyResults>,
+    clock: Res<RoundClock>,
+    score: Res<Score>,
+    run_config: Res<RunConfig>,
+) {
+    if !challenge.scored {
+        return;
+    }
+
+    results.0.insert(
+        challenge.day,
+        LeaderboardEntry {
+            score: score.0,
+            secs: clock.elapsed_secs(),
+            modifiers: *run_config,
+        },
+    );
+
+    if let Err(error) = results.save() {
+        warn!("Couldn't save the daily results: {error}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn to_date_counts_from_the_epoch() {
+        assert_eq!(Day(0).to_date(), (1970, 1, 1));
+        assert_eq!(Day(19_723).to_date(), (2024, 1, 1));
+        assert_eq!(Day(19_722).to_date(), (2023, 12, 31));
+    }
+
+    #[test]
+    fn to_date_handles_leap_days() {
+        assert_eq!(Day(11_016).to_date(), (2000, 2, 29));
+        assert_eq!(Day(11_017).to_date(), (2000, 3, 1));
+        // 2100 isn't a leap year.
+        assert_eq!(Day(47_540).to_date(), (2100, 2, 28));
+        assert_eq!(Day(47_541).to_date(), (2100, 3, 1));
+    }
+
+    #[test]
+    fn seed_matches_splitmix64() {
+        assert_eq!(Day(0).seed(), 0xE220_A839_7B1D_CDAF);
+        assert_eq!(Day(1).seed(), 0x910A_2DEC_8902_5CC1);
+    }
+
+    #[test]
+    fn neighbouring_days_get_different_seeds() {
+        assert_ne!(Day(19_723).seed(), Day(19_724).seed());
+    }
+}
@@ -0,0 +1,98 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    destruction::Destroyed, player::Player, power_up::PowerUps, projectile::Shot, stats::{Health, Points, Score, ScoreMultiplier}
+};
+
+pub mod boss;
+pub mod mine;
+pub mod saucer;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Enemy>();
+    app.add_systems(
+        Update,
+        (shot_enemies, collision_with_player, destroyed_enemies).chain(),
+    );
+    app.add_plugins(boss::plugin);
+    app.add_plugins(mine::plugin);
+    app.add_plugins(saucer::plugin);
+}
+
+/// Something hostile to the player, that can be
+/// shot down for its [`Points`], and that hurts
+/// the player when they crash into each other.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Enemy;
+
+fn shot_enemies(
+    mut shot_event_reader: EventReader<Shot>,
+    mut destroyed_event_writer: EventWriter<Destroyed>,
+    mut enemy_query: Query<&mut Health, With<Enemy>>,
+) {
+    for shot in shot_event_reader.read() {
+        let Ok(mut health) = enemy_query.get_mut(shot.entity) else {
+            continue;
+        };
+
+        health.sub(1);
+        if health.current() == 0 {
+            destroyed_event_writer.send(Destroyed(shot.entity));
+        }
+    }
+}
+
+/// Crashing into an enemy destroys it, without scoring
+/// its points, and hurts the player unless shielded.
+fn collision_with_player(
+    mut collision_event_reader: EventReader<CollisionStarted>,
+    mut destroyed_event_writer: EventWriter<Destroyed>,
+    mut player_query: Query<(&mut Health, &PowerUps), With<Player>>,
+    enemy_query: Query<(), With<Enemy>>,
+) {
+    for CollisionStarted(entity1, entity2) in collision_event_reader.read() {
+        let mut logic = |first_entity: &Entity, second_entity: &Entity| {
+            if !enemy_query.contains(*first_entity) {
+                return;
+            }
+
+            let Ok((mut health, power_ups)) = player_query.get_mut(*second_entity) else {
+                return;
+            };
+
+            if !power_ups.shielded() {
+                health.sub(1);
+                if health.current() == 0 {
+                    destroyed_event_writer.send(Destroyed(*second_entity));
+                }
+            }
+
+            destroyed_event_writer.send(Destroyed(*first_entity));
+        };
+
+        logic(entity1, entity2);
+        logic(entity2, entity1);
+    }
+}
+
+fn destroyed_enemies(
+    mut destroyed_event_reader: EventReader<Destroyed>,
+    enemy_query: Query<(&Health, &Points), With<Enemy>>,
+    mut score: ResMut<Score>,
+    score_multiplier: Res<ScoreMultiplier>,
+    mut commands: Commands,
+) {
+    for Destroyed(entity) in destroyed_event_reader.read() {
+        let Ok((health, points)) = enemy_query.get(*entity) else {
+            continue;
+        };
+
+        if health.current() == 0 {
+            score.0 += points.0 * score_multiplier.0;
+        }
+
+        commands.entity(*entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,479 @@
+use std::{f32::consts::TAU, time::Duration};
+
+use avian2d::prelude::*;
+use bevy::{
+    color::palettes::css::{CRIMSON, DARK_SLATE_GRAY, MEDIUM_PURPLE, RED, SKY_BLUE, WHITE},
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+use bevy_transform_interpolation::*;
+use sickle_ui::prelude::*;
+
+use crate::{
+    asteroid::asteroid_spawner::spawners_enabled, destruction::Destroyed, explosion::HitAsShot, faction::Faction, game_mode::enemies_enabled, player::Player, power_up::PowerUps, projectile::{Shootable, Shot, SpawnProjectile}, states::GameState, stats::{Health, Points, Score, ScoreMultiplier}, wave::WaveStarted, TOP_VIEWPORT_EDGE
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Boss>();
+    app.register_type::<BossPart>();
+    app.observe(spawn_boss);
+    app.add_systems(FixedUpdate, move_boss.run_if(in_state(GameState::Playing)));
+    app.add_systems(
+        Update,
+        (
+            boss_milestone.run_if(enemies_enabled.and_then(spawners_enabled)),
+            boss_part_shoot,
+            (shot_boss_parts, boss_collision_with_player, destroyed_boss_parts).chain(),
+            (update_boss_health_bar, visualize_boss_shield),
+        )
+            .run_if(in_state(GameState::Playing)),
+    );
+}
+
+/// A boss shows up at the start of every wave
+/// that's a multiple of this.
+const BOSS_WAVE_INTERVAL: u32 = 3;
+
+/// A mothership made up of turrets around a shielded core,
+/// each of which is a child entity that's shot down on its own.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Boss {
+    phase: BossPhase,
+}
+
+/// The boss's attacks get more dangerous
+/// as more of its parts are destroyed.
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+enum BossPhase {
+    /// The turrets take aimed shots,
+    /// while the core is shielded.
+    Turrets,
+    /// With half of its turrets down, the turrets
+    /// fire faster and the core fires bursts too.
+    Enraged,
+    /// With all of its turrets down, the core's shield
+    /// drops and it fires bursts rapidly.
+    Exposed,
+}
+
+impl BossPhase {
+    fn from_turrets(remaining: usize, total: usize) -> Self {
+        if remaining == 0 {
+            BossPhase::Exposed
+        } else if remaining * 2 <= total {
+            BossPhase::Enraged
+        } else {
+            BossPhase::Turrets
+        }
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct BossPart {
+    kind: BossPartKind,
+    shoot_timer: Timer,
+}
+
+#[derive(Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+enum BossPartKind {
+    Turret,
+    Core,
+}
+
+impl BossPartKind {
+    /// How long between attacks in the given phase,
+    /// or `None` if this part doesn't attack in it.
+    fn get_shoot_secs(&self, phase: BossPhase) -> Option<f32> {
+        match (self, phase) {
+            (BossPartKind::Turret, BossPhase::Turrets) => Some(2.0),
+            (BossPartKind::Turret, _) => Some(1.0),
+            (BossPartKind::Core, BossPhase::Turrets) => None,
+            (BossPartKind::Core, BossPhase::Enraged) => Some(3.0),
+            (BossPartKind::Core, BossPhase::Exposed) => Some(1.2),
+        }
+    }
+}
+
+const TURRET_POSITIONS: [Vec2; 4] = [
+    Vec2::new(-90., 0.),
+    Vec2::new(90., 0.),
+    Vec2::new(-45., -40.),
+    Vec2::new(45., -40.),
+];
+const TURRET_RADIUS: f32 = 18.;
+const TURRET_HEALTH: u16 = 4;
+const CORE_RADIUS: f32 = 36.;
+const CORE_HEALTH: u16 = 12;
+
+/// The combined health of every part of a fresh boss.
+const BOSS_MAX_HEALTH: u16 = TURRET_HEALTH * TURRET_POSITIONS.len() as u16 + CORE_HEALTH;
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct BossHealthBar(Entity);
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct BossHealthBarFill;
+
+#[derive(Event, Debug)]
+pub struct SpawnBoss;
+
+fn boss_milestone(
+    mut wave_started_event_reader: EventReader<WaveStarted>,
+    boss_query: Query<(), With<Boss>>,
+    mut commands: Commands,
+) {
+    for WaveStarted(wave) in wave_started_event_reader.read() {
+        if wave % BOSS_WAVE_INTERVAL == 0 && boss_query.is_empty() {
+            commands.trigger(SpawnBoss);
+        }
+    }
+}
+
+fn spawn_boss(
+    _: Trigger<SpawnBoss>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    const SPAWN_OFFSET: f32 = 100.;
+    const TURRET_POINTS: u32 = 5;
+    const CORE_POINTS: u32 = 50;
+
+    let turret_mesh = Mesh2dHandle(meshes.add(Circle::new(TURRET_RADIUS)));
+    let turret_material = materials.add(Color::from(CRIMSON));
+
+    let boss = commands
+        .spawn((
+            Name::new("Boss"),
+            StateScoped(GameState::Playing),
+            Boss {
+                phase: BossPhase::Turrets,
+            },
+            RigidBody::Kinematic,
+            TranslationInterpolation,
+            MaterialMesh2dBundle {
+                transform: Transform::from_xyz(0., TOP_VIEWPORT_EDGE + SPAWN_OFFSET, 0.),
+                mesh: Mesh2dHandle(meshes.add(Ellipse::new(130., 40.))),
+                material: materials.add(Color::from(DARK_SLATE_GRAY)),
+                ..default()
+            },
+        ))
+        .with_children(|children| {
+            let part = |kind: BossPartKind| BossPart {
+                kind,
+                shoot_timer: Timer::from_seconds(1., TimerMode::Repeating),
+            };
+
+            children.spawn((
+                Name::new("BossCore"),
+                part(BossPartKind::Core),
+                Shootable,
+                HitAsShot,
+                Faction::Enemy,
+                Health::new(CORE_HEALTH),
+                Points(CORE_POINTS),
+                Collider::circle(CORE_RADIUS),
+                MaterialMesh2dBundle {
+                    transform: Transform::from_xyz(0., 0., 1.),
+                    mesh: Mesh2dHandle(meshes.add(Circle::new(CORE_RADIUS))),
+                    material: materials.add(Color::from(MEDIUM_PURPLE)),
+                    ..default()
+                },
+            ));
+
+            for position in TURRET_POSITIONS {
+                children.spawn((
+                    Name::new("BossTurret"),
+                    part(BossPartKind::Turret),
+                    Shootable,
+                    HitAsShot,
+                    Faction::Enemy,
+                    Health::new(TURRET_HEALTH),
+                    Points(TURRET_POINTS),
+                    Collider::circle(TURRET_RADIUS),
+                    MaterialMesh2dBundle {
+                        transform: Transform::from_translation(position.extend(1.)),
+                        mesh: turret_mesh.clone(),
+                        material: turret_material.clone(),
+                        ..default()
+                    },
+                ));
+            }
+        })
+        .id();
+
+    // Health bar.
+    commands
+        .ui_builder(UiRoot)
+        .column(|column| {
+            column
+                .spawn((NodeBundle::default(), BossHealthBarFill))
+                .style()
+                .width(Val::Percent(100.))
+                .height(Val::Percent(100.))
+                .background_color(RED.into());
+        })
+        .style()
+        .position_type(PositionType::Absolute)
+        .top(Val::Px(16.))
+        .left(Val::Percent(25.))
+        .width(Val::Percent(50.))
+        .height(Val::Px(12.))
+        .border(UiRect::all(Val::Px(2.)))
+        .border_color(WHITE.into())
+        .entity_commands()
+        .insert((BossHealthBar(boss), StateScoped(GameState::Playing)));
+}
+
+/// Fly the boss down into view, and
+/// then sway it from side to side.
+fn move_boss(
+    mut query: Query<(&Boss, &Transform, &mut LinearVelocity)>,
+    time: Res<Time<Fixed>>,
+) {
+    const HOVER_HEIGHT: f32 = TOP_VIEWPORT_EDGE - 140.;
+    const ENTRY_SPEED: f32 = 80.;
+    const SWAY_DISTANCE: f32 = 360.;
+    const SWAY_SECS: f32 = 8.;
+
+    for (boss, transform, mut linear_velocity) in &mut query {
+        let sway_speed = match boss.phase {
+            BossPhase::Turrets | BossPhase::Enraged => 1.,
+            BossPhase::Exposed => 2.,
+        };
+
+        let angle = time.elapsed_seconds() * TAU / SWAY_SECS * sway_speed;
+        let target_x = angle.sin() * SWAY_DISTANCE;
+
+        linear_velocity.0 = Vec2::new(
+            target_x - transform.translation.x,
+            if transform.translation.y > HOVER_HEIGHT {
+                -ENTRY_SPEED
+            } else {
+                0.
+            },
+        );
+    }
+}
+
+fn boss_part_shoot(
+    mut part_query: Query<(&mut BossPart, &GlobalTransform, &Parent)>,
+    boss_query: Query<&Boss>,
+    player_query: Query<&Transform, With<Player>>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    const CORE_BURST_SHOTS: usize = 12;
+    const PROJECTILE_SPAWN_OFFSET: f32 = 10.;
+
+    for (mut part, transform, parent) in &mut part_query {
+        let Ok(boss) = boss_query.get(parent.get()) else {
+            continue;
+        };
+
+        let Some(shoot_secs) = part.kind.get_shoot_secs(boss.phase) else {
+            continue;
+        };
+
+        part.shoot_timer.set_duration(Duration::from_secs_f32(shoot_secs));
+        part.shoot_timer.tick(time.delta());
+        if !part.shoot_timer.just_finished() {
+            continue;
+        }
+
+        let position = transform.translation().xy();
+        let directions: Vec<Vec2> = match part.kind {
+            BossPartKind::Turret => {
+                let Some(player_transform) = player_query.iter().min_by(|a, b| {
+                    a.translation
+                        .xy()
+                        .distance_squared(position)
+                        .total_cmp(&b.translation.xy().distance_squared(position))
+                }) else {
+                    continue;
+                };
+                vec![(player_transform.translation.xy() - position).normalize_or_zero()]
+            }
+            BossPartKind::Core => (0..CORE_BURST_SHOTS)
+                .map(|n| Vec2::from_angle(n as f32 * TAU / CORE_BURST_SHOTS as f32))
+                .collect(),
+        };
+
+        let radius = match part.kind {
+            BossPartKind::Turret => TURRET_RADIUS,
+            BossPartKind::Core => CORE_RADIUS,
+        };
+
+        for direction in directions {
+            let offset = direction * (radius + PROJECTILE_SPAWN_OFFSET);
+            commands.trigger(
+                SpawnProjectile::new(
+                    (position + offset).extend(0.),
+                    Quat::from_rotation_arc(Vec3::Y, direction.extend(0.)),
+                )
+                .with_faction(Faction::Enemy),
+            );
+        }
+    }
+}
+
+fn shot_boss_parts(
+    mut shot_event_reader: EventReader<Shot>,
+    mut destroyed_event_writer: EventWriter<Destroyed>,
+    mut part_query: Query<(&BossPart, &mut Health, &Parent)>,
+    boss_query: Query<&Boss>,
+) {
+    for shot in shot_event_reader.read() {
+        let Ok((part, mut health, parent)) = part_query.get_mut(shot.entity) else {
+            continue;
+        };
+
+        // The core is shielded until every turret is down.
+        let exposed = boss_query
+            .get(parent.get())
+            .is_ok_and(|boss| boss.phase == BossPhase::Exposed);
+        if part.kind == BossPartKind::Core && !exposed {
+            continue;
+        }
+
+        if health.current() == 0 {
+            continue;
+        }
+
+        health.sub(1);
+        if health.current() == 0 {
+            destroyed_event_writer.send(Destroyed(shot.entity));
+        }
+    }
+}
+
+/// Touching any part of the boss hurts the player,
+/// but unlike smaller enemies the boss survives it.
+fn boss_collision_with_player(
+    mut collision_event_reader: EventReader<CollisionStarted>,
+    mut destroyed_event_writer: EventWriter<Destroyed>,
+    mut player_query: Query<(&mut Health, &PowerUps), With<Player>>,
+    part_query: Query<(), With<BossPart>>,
+) {
+    for CollisionStarted(entity1, entity2) in collision_event_reader.read() {
+        let mut logic = |first_entity: &Entity, second_entity: &Entity| {
+            if !part_query.contains(*first_entity) {
+                return;
+            }
+
+            let Ok((mut health, power_ups)) = player_query.get_mut(*second_entity) else {
+                return;
+            };
+
+            if power_ups.shielded() {
+                return;
+            }
+
+            health.sub(1);
+            if health.current() == 0 {
+                destroyed_event_writer.send(Destroyed(*second_entity));
+            }
+        };
+
+        logic(entity1, entity2);
+        logic(entity2, entity1);
+    }
+}
+
+fn destroyed_boss_parts(
+    mut destroyed_event_reader: EventReader<Destroyed>,
+    part_query: Query<(&BossPart, &Health, &Points, &Parent)>,
+    mut boss_query: Query<(&mut Boss, &Children)>,
+    mut score: ResMut<Score>,
+    score_multiplier: Res<ScoreMultiplier>,
+    mut commands: Commands,
+) {
+    for Destroyed(entity) in destroyed_event_reader.read() {
+        let Ok((part, health, points, parent)) = part_query.get(*entity) else {
+            continue;
+        };
+
+        if health.current() != 0 {
+            continue;
+        }
+
+        score.0 += points.0 * score_multiplier.0;
+
+        let Ok((mut boss, children)) = boss_query.get_mut(parent.get()) else {
+            continue;
+        };
+
+        if part.kind == BossPartKind::Core {
+            commands.entity(parent.get()).despawn_recursive();
+            continue;
+        }
+
+        commands.entity(*entity).despawn_recursive();
+
+        let remaining_turrets = children
+            .iter()
+            .filter_map(|child| part_query.get(*child).ok())
+            .filter(|(part, health, ..)| {
+                part.kind == BossPartKind::Turret && health.current() > 0
+            })
+            .count();
+        boss.phase = BossPhase::from_turrets(remaining_turrets, TURRET_POSITIONS.len());
+    }
+}
+
+/// Size the health bar to the combined health of every
+/// part of the boss, and remove it once the boss is gone.
+fn update_boss_health_bar(
+    health_bar_query: Query<(Entity, &BossHealthBar, &Children)>,
+    mut fill_query: Query<&mut Style, With<BossHealthBarFill>>,
+    boss_query: Query<&Children, With<Boss>>,
+    part_query: Query<&Health, With<BossPart>>,
+    mut commands: Commands,
+) {
+    for (entity, BossHealthBar(boss), health_bar_children) in &health_bar_query {
+        let Ok(boss_children) = boss_query.get(*boss) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        // Destroyed parts are despawned, so the total has to be
+        // measured against a full boss rather than the parts left.
+        let current: u16 = part_query
+            .iter_many(boss_children)
+            .map(|health| health.current())
+            .sum();
+
+        let mut fills = fill_query.iter_many_mut(health_bar_children);
+        while let Some(mut style) = fills.fetch_next() {
+            style.width = Val::Percent(current as f32 / BOSS_MAX_HEALTH as f32 * 100.);
+        }
+    }
+}
+
+/// Use gizmos to render a ring around
+/// the boss's core while it is shielded.
+fn visualize_boss_shield(
+    mut gizmos: Gizmos,
+    part_query: Query<(&BossPart, &GlobalTransform, &Parent)>,
+    boss_query: Query<&Boss>,
+) {
+    const SHIELD_GAP: f32 = 6.;
+
+    for (part, transform, parent) in &part_query {
+        let Ok(boss) = boss_query.get(parent.get()) else {
+            continue;
+        };
+
+        if part.kind == BossPartKind::Core && boss.phase != BossPhase::Exposed {
+            gizmos.circle_2d(
+                transform.translation().xy(),
+                CORE_RADIUS + SHIELD_GAP,
+                SKY_BLUE,
+            );
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use avian2d::prelude::*;
+use bevy::{
+    color::palettes::css::{DARK_SLATE_GRAY, RED},
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+use bevy_transform_interpolation::*;
+
+use crate::{
+    asteroid::asteroid_spawner::SpawnMines, destruction::Destroyed, explosion::Explode, faction::Faction, game_mode::enemies_enabled, player::Player, projectile::Shootable, states::GameState, stats::{Health, Points}, viewport_bound::DestroyOutOfBounds, wave::WaveStarted
+};
+
+use super::Enemy;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Mine>();
+    app.observe(spawn_mine);
+    app.add_systems(
+        Update,
+        (
+            mine_milestone.run_if(enemies_enabled),
+            (arm_mines, detonate_mines).chain(),
+            shot_mines.before(super::destroyed_enemies),
+            visualize_mines,
+        )
+            .run_if(in_state(GameState::Playing)),
+    );
+}
+
+/// How close the player has to get to
+/// an armed mine for it to go off.
+const MINE_TRIGGER_RADIUS: f32 = 90.;
+const MINE_EXPLOSION_RADIUS: f32 = 120.;
+const MINE_EXPLOSION_DAMAGE: u16 = 1;
+
+/// A mine that blows up when the player comes near it,
+/// or when it's shot, taking anything close by with it.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Mine {
+    /// Mines only go off once armed, so one
+    /// can't be dropped right next to the player.
+    arm_timer: Timer,
+}
+
+impl Mine {
+    fn is_armed(&self) -> bool {
+        self.arm_timer.finished()
+    }
+}
+
+#[derive(Event, Debug)]
+pub struct SpawnMine {
+    position: Vec3,
+    velocity: Vec2,
+}
+
+impl SpawnMine {
+    pub fn new(position: Vec3) -> Self {
+        Self {
+            position,
+            velocity: Vec2::ZERO,
+        }
+    }
+
+    pub fn with_velocity(mut self, velocity: Vec2) -> Self {
+        self.velocity = velocity;
+        self
+    }
+}
+
+/// Lay some mines at the start of every wave after
+/// the first, with more of them the further in it is.
+fn mine_milestone(
+    mut wave_started_event_reader: EventReader<WaveStarted>,
+    mut commands: Commands,
+) {
+    const FIRST_MINE_WAVE: u32 = 2;
+    const MAX_MINES: u32 = 6;
+
+    for WaveStarted(wave) in wave_started_event_reader.read() {
+        if *wave < FIRST_MINE_WAVE {
+            continue;
+        }
+
+        let amount = (wave - FIRST_MINE_WAVE + 1).min(MAX_MINES);
+        commands.trigger(SpawnMines::new(amount as u16));
+    }
+}
+
+fn spawn_mine(
+    trigger: Trigger<SpawnMine>,
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    const MINE_RADIUS: f32 = 14.;
+    const MINE_SIDES: usize = 8;
+    const ARM_SECS: f32 = 2.;
+    const MINE_POINTS: u32 = 2;
+
+    let event = trigger.event();
+
+    let mut mine = commands.spawn((
+        Name::new("Mine"),
+        StateScoped(GameState::Playing),
+        Mine {
+            arm_timer: Timer::from_seconds(ARM_SECS, TimerMode::Once),
+        },
+        Enemy,
+        Shootable,
+        Faction::Enemy,
+        Health::new(1),
+        Points(MINE_POINTS),
+        RigidBody::Kinematic,
+        Collider::regular_polygon(MINE_RADIUS, MINE_SIDES),
+        LinearVelocity(event.velocity),
+        TranslationInterpolation,
+        MaterialMesh2dBundle {
+            transform: Transform::from_translation(event.position),
+            mesh: Mesh2dHandle(meshes.add(RegularPolygon::new(MINE_RADIUS, MINE_SIDES))),
+            material: materials.add(Color::from(DARK_SLATE_GRAY)),
+            ..default()
+        },
+    ));
+
+    // Mines drifting in from the edges would otherwise
+    // float off forever once they cross the screen.
+    if event.velocity != Vec2::ZERO {
+        mine.insert(DestroyOutOfBounds);
+    }
+}
+
+fn arm_mines(mut query: Query<&mut Mine>, time: Res<Time>) {
+    for mut mine in &mut query {
+        mine.arm_timer.tick(time.delta());
+    }
+}
+
+/// Armed mines go off when any player comes within range.
+///
+/// The mine is removed before the explosion goes off, so
+/// that it doesn't get caught in it and score its points.
+fn detonate_mines(
+    mine_query: Query<(Entity, &Mine, &Transform)>,
+    player_query: Query<&Transform, With<Player>>,
+    mut commands: Commands,
+) {
+    for (entity, mine, transform) in &mine_query {
+        let player_in_range = player_query.iter().any(|player_transform| {
+            transform.translation.distance(player_transform.translation) <= MINE_TRIGGER_RADIUS
+        });
+        if !mine.is_armed() || !player_in_range {
+            continue;
+        }
+
+        commands.entity(entity).despawn_recursive();
+        commands.trigger(Explode::new(
+            transform.translation,
+            MINE_EXPLOSION_RADIUS,
+            MINE_EXPLOSION_DAMAGE,
+        ));
+    }
+}
+
+/// Mines that are shot down, or caught in another
+/// explosion, go off where they are, armed or not.
+///
+/// Their points and clean up are left to the [`Enemy`] systems.
+fn shot_mines(
+    mut destroyed_event_reader: EventReader<Destroyed>,
+    mine_query: Query<(&Transform, &Health), With<Mine>>,
+    mut commands: Commands,
+) {
+    for Destroyed(entity) in destroyed_event_reader.read() {
+        let Ok((transform, health)) = mine_query.get(*entity) else {
+            continue;
+        };
+
+        // Crashing into a mine destroys it without it being
+        // worn down, and that already hurt the player.
+        if health.current() > 0 {
+            continue;
+        }
+
+        commands.trigger(Explode::new(
+            transform.translation,
+            MINE_EXPLOSION_RADIUS,
+            MINE_EXPLOSION_DAMAGE,
+        ));
+    }
+}
+
+/// Show the trigger radius of armed mines as a blinking ring.
+fn visualize_mines(query: Query<(&Mine, &Transform)>, time: Res<Time>, mut gizmos: Gizmos) {
+    const BLINKS_PER_SEC: f32 = 2.;
+
+    if (time.elapsed_seconds() * BLINKS_PER_SEC).fract() > 0.5 {
+        return;
+    }
+
+    for (mine, transform) in &query {
+        if !mine.is_armed() {
+            continue;
+        }
+
+        gizmos.circle_2d(
+            transform.translation.xy(),
+            MINE_TRIGGER_RADIUS,
+            RED.with_alpha(0.4),
+        );
+    }
+}
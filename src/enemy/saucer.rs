@@ -0,0 +1,318 @@
+use avian2d::prelude::*;
+use bevy::{
+    color::palettes::css::{LIGHT_GRAY, LIGHT_GREEN},
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+};
+use bevy_transform_interpolation::*;
+use rand::Rng;
+
+use crate::{
+    asteroid::asteroid_spawner::spawners_enabled, faction::Faction, game_mode::enemies_enabled, player::Player, projectile::{Shootable, SpawnProjectile, PROJECTILE_SPEED}, random::GameRng, states::GameState, stats::{Health, Points, Score}, viewport_bound::DestroyOutOfBounds, LEFT_VIEWPORT_EDGE, RIGHT_VIEWPORT_EDGE, TOP_VIEWPORT_EDGE
+};
+
+use super::Enemy;
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Saucer>();
+    app.init_resource::<SaucerSpawnTimer>();
+    app.observe(spawn_saucer);
+    app.add_systems(OnEnter(GameState::Playing), reset_saucer_spawn_timer);
+    // Spawning is timed on the fixed clock, so that a seeded
+    // round brings in its saucers on the same ticks every time.
+    app.add_systems(
+        FixedUpdate,
+        (move_saucers, saucer_spawn_tick.run_if(enemies_enabled.and_then(spawners_enabled)))
+            .run_if(in_state(GameState::Playing)),
+    );
+    app.add_systems(
+        Update,
+        saucer_shoot.run_if(in_state(GameState::Playing)),
+    );
+}
+
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq)]
+#[reflect(Component)]
+pub enum SaucerKind {
+    /// Big, slow, and fires in random directions.
+    Large,
+    /// Small, quick, and aims at the player.
+    Small,
+}
+
+impl SaucerKind {
+    fn get_name(&self) -> String {
+        String::from(match self {
+            SaucerKind::Large => "LargeSaucer",
+            SaucerKind::Small => "SmallSaucer",
+        })
+    }
+
+    /// Half the width and height of the saucer's hull.
+    fn get_half_size(&self) -> Vec2 {
+        match self {
+            SaucerKind::Large => Vec2::new(36., 14.),
+            SaucerKind::Small => Vec2::new(20., 8.),
+        }
+    }
+
+    fn get_speed(&self) -> f32 {
+        match self {
+            SaucerKind::Large => 120.,
+            SaucerKind::Small => 180.,
+        }
+    }
+
+    fn get_points(&self) -> u32 {
+        match self {
+            SaucerKind::Large => 5,
+            SaucerKind::Small => 10,
+        }
+    }
+
+    fn get_shoot_secs(&self) -> f32 {
+        match self {
+            SaucerKind::Large => 1.5,
+            SaucerKind::Small => 1.0,
+        }
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Saucer {
+    kind: SaucerKind,
+    /// Which way the saucer crosses the screen; 1 for right, -1 for left.
+    horizontal_direction: f32,
+    /// Which way the saucer is currently zigzagging; 1 for up,
+    /// -1 for down, and 0 for flying straight across.
+    vertical_direction: f32,
+    zigzag_timer: Timer,
+    shoot_timer: Timer,
+}
+
+/// Counts down to the next saucer showing up,
+/// which happens sooner the higher the score is.
+#[derive(Resource)]
+struct SaucerSpawnTimer(Timer);
+
+impl Default for SaucerSpawnTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            get_saucer_spawn_secs(0),
+            TimerMode::Once,
+        ))
+    }
+}
+
+fn get_saucer_spawn_secs(score: u32) -> f32 {
+    const BASE_SECS: f32 = 30.;
+    const MIN_SECS: f32 = 8.;
+    const SECS_PER_POINT: f32 = 0.25;
+
+    (BASE_SECS - score as f32 * SECS_PER_POINT).max(MIN_SECS)
+}
+
+/// How likely the next saucer is to be a small one.
+fn get_small_saucer_chance(score: u32) -> f64 {
+    const MIN_CHANCE: f64 = 0.1;
+    const MAX_CHANCE: f64 = 0.8;
+    const POINTS_TO_MAX_CHANCE: f64 = 100.;
+
+    (score as f64 / POINTS_TO_MAX_CHANCE).clamp(MIN_CHANCE, MAX_CHANCE)
+}
+
+#[derive(Event, Debug)]
+pub struct SpawnSaucer {
+    kind: SaucerKind,
+}
+
+impl SpawnSaucer {
+    pub fn new(kind: SaucerKind) -> Self {
+        Self { kind }
+    }
+}
+
+fn reset_saucer_spawn_timer(mut commands: Commands) {
+    commands.insert_resource(SaucerSpawnTimer::default());
+}
+
+fn saucer_spawn_tick(
+    mut spawn_timer: ResMut<SaucerSpawnTimer>,
+    time: Res<Time>,
+    score: Res<Score>,
+    mut game_rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    spawn_timer.0.tick(time.delta());
+    if !spawn_timer.0.just_finished() {
+        return;
+    }
+
+    let kind = if game_rng.saucers().gen_bool(get_small_saucer_chance(score.0)) {
+        SaucerKind::Small
+    } else {
+        SaucerKind::Large
+    };
+    commands.trigger(SpawnSaucer::new(kind));
+
+    spawn_timer.0 = Timer::from_seconds(get_saucer_spawn_secs(score.0), TimerMode::Once);
+}
+
+fn spawn_saucer(
+    trigger: Trigger<SpawnSaucer>,
+    mut commands: Commands,
+    mut game_rng: ResMut<GameRng>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    const ZIGZAG_SECS: f32 = 1.2;
+    const SPAWN_OFFSET: f32 = 40.;
+    const SPAWN_HEIGHT_RANGE: f32 = TOP_VIEWPORT_EDGE * 0.7;
+
+    let kind = trigger.event().kind;
+    let half_size = kind.get_half_size();
+
+    let from_left: bool = game_rng.saucers().gen();
+    let (x, horizontal_direction) = if from_left {
+        (LEFT_VIEWPORT_EDGE - SPAWN_OFFSET, 1.)
+    } else {
+        (RIGHT_VIEWPORT_EDGE + SPAWN_OFFSET, -1.)
+    };
+    let y = game_rng.saucers().gen_range(-SPAWN_HEIGHT_RANGE..=SPAWN_HEIGHT_RANGE);
+
+    commands
+        .spawn((
+            Name::new(kind.get_name()),
+            StateScoped(GameState::Playing),
+            Saucer {
+                kind,
+                horizontal_direction,
+                vertical_direction: 0.,
+                zigzag_timer: Timer::from_seconds(ZIGZAG_SECS, TimerMode::Repeating),
+                shoot_timer: Timer::from_seconds(kind.get_shoot_secs(), TimerMode::Repeating),
+            },
+            Enemy,
+            Shootable,
+            Faction::Enemy,
+            Health::new(1),
+            Points(kind.get_points()),
+            DestroyOutOfBounds,
+            RigidBody::Kinematic,
+            Collider::ellipse(half_size.x, half_size.y),
+            TranslationInterpolation,
+            MaterialMesh2dBundle {
+                transform: Transform::from_xyz(x, y, 0.),
+                mesh: Mesh2dHandle(meshes.add(Ellipse::new(half_size.x, half_size.y))),
+                material: materials.add(Color::from(LIGHT_GRAY)),
+                ..default()
+            },
+        ))
+        .with_children(|children| {
+            children.spawn((
+                Name::new("SaucerDome"),
+                MaterialMesh2dBundle {
+                    transform: Transform::from_xyz(0., half_size.y * 0.6, -1.),
+                    mesh: Mesh2dHandle(meshes.add(Circle::new(half_size.y))),
+                    material: materials.add(Color::from(LIGHT_GREEN)),
+                    ..default()
+                },
+            ));
+        });
+}
+
+/// Fly the saucers across the screen, randomly
+/// changing between going up, down, and straight across.
+fn move_saucers(
+    mut query: Query<(&mut Saucer, &Transform, &mut LinearVelocity)>,
+    time: Res<Time<Fixed>>,
+    mut game_rng: ResMut<GameRng>,
+) {
+    // How far from the top and bottom edges the saucers
+    // turn back around, so they don't leave the screen.
+    const VERTICAL_LIMIT: f32 = TOP_VIEWPORT_EDGE * 0.8;
+    // How fast the saucers move vertically,
+    // relative to their horizontal speed.
+    const VERTICAL_SPEED_MODIFIER: f32 = 0.6;
+
+    for (mut saucer, transform, mut linear_velocity) in &mut query {
+        saucer.zigzag_timer.tick(time.delta());
+        if saucer.zigzag_timer.just_finished() {
+            saucer.vertical_direction = game_rng.saucers().gen_range(-1..=1) as f32;
+        }
+
+        let y = transform.translation.y;
+        if (y > VERTICAL_LIMIT && saucer.vertical_direction > 0.)
+            || (y < -VERTICAL_LIMIT && saucer.vertical_direction < 0.)
+        {
+            saucer.vertical_direction = -saucer.vertical_direction;
+        }
+
+        let speed = saucer.kind.get_speed();
+        linear_velocity.0 = Vec2::new(
+            saucer.horizontal_direction * speed,
+            saucer.vertical_direction * speed * VERTICAL_SPEED_MODIFIER,
+        );
+    }
+}
+
+fn saucer_shoot(
+    mut saucer_query: Query<(&mut Saucer, &Transform)>,
+    player_query: Query<(&Transform, &LinearVelocity), With<Player>>,
+    time: Res<Time>,
+    mut game_rng: ResMut<GameRng>,
+    mut commands: Commands,
+) {
+    const PROJECTILE_SPAWN_OFFSET: f32 = 10.;
+
+    for (mut saucer, transform) in &mut saucer_query {
+        saucer.shoot_timer.tick(time.delta());
+        if !saucer.shoot_timer.just_finished() {
+            continue;
+        }
+
+        let position = transform.translation.xy();
+        // Small saucers go after whichever player is nearest.
+        let player = player_query.iter().min_by(|(a, _), (b, _)| {
+            a.translation
+                .xy()
+                .distance_squared(position)
+                .total_cmp(&b.translation.xy().distance_squared(position))
+        });
+
+        let direction = match (saucer.kind, player) {
+            (SaucerKind::Small, Some((player_transform, player_velocity))) => lead_target(
+                position,
+                player_transform.translation.xy(),
+                player_velocity.0,
+            ),
+            _ => Vec2::from_angle(game_rng.saucers().gen_range(0.0..std::f32::consts::TAU)),
+        };
+
+        let offset = direction * (saucer.kind.get_half_size().x + PROJECTILE_SPAWN_OFFSET);
+
+        commands.trigger(
+            SpawnProjectile::new(
+                (position + offset).extend(0.),
+                Quat::from_rotation_arc(Vec3::Y, direction.extend(0.)),
+            )
+            .with_faction(Faction::Enemy),
+        );
+    }
+}
+
+/// The direction to fire in from `origin` to hit a
+/// target moving in a straight line at `target_velocity`.
+fn lead_target(origin: Vec2, target: Vec2, target_velocity: Vec2) -> Vec2 {
+    // Refining the time to impact a few times
+    // is close enough at projectile speeds.
+    const ITERATIONS: usize = 3;
+
+    let mut aim = target;
+    for _ in 0..ITERATIONS {
+        let time = origin.distance(aim) / PROJECTILE_SPEED;
+        aim = target + target_velocity * time;
+    }
+
+    (aim - origin).normalize_or_zero()
+}
@@ -0,0 +1,152 @@
+use bevy::{color::palettes::css::ORANGE, prelude::*};
+
+use crate::{
+    destruction::Destroyed, player::Player, power_up::PowerUps, projectile::{Shootable, Shot}, states::GameState, stats::Health
+};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<ExplosionEffect>();
+    app.register_type::<HitAsShot>();
+    app.observe(explode);
+    app.add_systems(
+        Update,
+        (explosion_effect_tick, visualize_explosion_effects).chain(),
+    );
+}
+
+/// Deal damage to every [`Shootable`] and [`Player`]
+/// within the radius of a point, or shoot it, if it's [`HitAsShot`].
+#[derive(Event, Debug)]
+pub struct Explode {
+    position: Vec3,
+    radius: f32,
+    damage: u16,
+    /// Whether players are left unhurt, such as
+    /// by an asteroid's explosion in a zen run.
+    spares_players: bool,
+}
+
+impl Explode {
+    pub fn new(position: Vec3, radius: f32, damage: u16) -> Self {
+        Self {
+            position,
+            radius,
+            damage,
+            spares_players: false,
+        }
+    }
+
+    pub fn with_players_spared(mut self, spares_players: bool) -> Self {
+        self.spares_players = spares_players;
+        self
+    }
+}
+
+/// Marks something that decides for itself whether it can be
+/// hurt when shot, such as the boss's parts, so explosions
+/// hit it with a [`Shot`] rather than damaging it directly.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct HitAsShot;
+
+/// The expanding ring drawn where an explosion went off.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ExplosionEffect {
+    timer: Timer,
+    radius: f32,
+}
+
+#[allow(clippy::type_complexity)]
+fn explode(
+    trigger: Trigger<Explode>,
+    mut query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &mut Health,
+            Option<&PowerUps>,
+            Has<Player>,
+            Has<HitAsShot>,
+        ),
+        Or<(With<Shootable>, With<Player>)>,
+    >,
+    mut destroyed_event_writer: EventWriter<Destroyed>,
+    mut shot_event_writer: EventWriter<Shot>,
+    mut commands: Commands,
+) {
+    const EXPLOSION_EFFECT_SECS: f32 = 0.3;
+
+    let event = trigger.event();
+
+    for (entity, global_transform, mut health, power_ups, is_player, hit_as_shot) in &mut query {
+        // Anything already at zero health has been
+        // destroyed and is waiting to be cleaned up,
+        // so it shouldn't be destroyed a second time.
+        if health.current() == 0 {
+            continue;
+        }
+
+        if power_ups.is_some_and(PowerUps::shielded) || (is_player && event.spares_players) {
+            continue;
+        }
+
+        // Children, like the boss's parts, are only
+        // where they are through their parent.
+        let offset = global_transform.translation().xy() - event.position.xy();
+        if offset.length() > event.radius {
+            continue;
+        }
+
+        if hit_as_shot {
+            let direction = offset.normalize_or_zero();
+            shot_event_writer.send(Shot {
+                entity,
+                direction,
+                local_normal: (global_transform.compute_transform().rotation.inverse()
+                    * -direction.extend(0.))
+                .xy(),
+                shooter: None,
+            });
+            continue;
+        }
+
+        health.sub(event.damage);
+        if health.current() == 0 {
+            destroyed_event_writer.send(Destroyed(entity));
+        }
+    }
+
+    commands.spawn((
+        Name::new("ExplosionEffect"),
+        StateScoped(GameState::Playing),
+        ExplosionEffect {
+            timer: Timer::from_seconds(EXPLOSION_EFFECT_SECS, TimerMode::Once),
+            radius: event.radius,
+        },
+        TransformBundle::from_transform(Transform::from_translation(event.position)),
+    ));
+}
+
+fn explosion_effect_tick(
+    mut query: Query<(&mut ExplosionEffect, Entity)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (mut explosion_effect, entity) in &mut query {
+        explosion_effect.timer.tick(time.delta());
+        if explosion_effect.timer.finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+fn visualize_explosion_effects(mut gizmos: Gizmos, query: Query<(&ExplosionEffect, &Transform)>) {
+    for (explosion_effect, transform) in &query {
+        gizmos.circle_2d(
+            transform.translation.xy(),
+            explosion_effect.radius * explosion_effect.timer.fraction(),
+            ORANGE,
+        );
+    }
+}
@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Faction>();
+    app.register_type::<FactionMatrix>();
+    app.init_resource::<FactionMatrix>();
+}
+
+/// The side something is on, which decides what its
+/// projectiles can damage, and what can damage it.
+///
+/// Anything without a faction is treated as [`Faction::Neutral`].
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[reflect(Component)]
+pub enum Faction {
+    Player,
+    Enemy,
+    #[default]
+    Neutral,
+}
+
+impl Faction {
+    fn index(&self) -> usize {
+        match self {
+            Faction::Player => 0,
+            Faction::Enemy => 1,
+            Faction::Neutral => 2,
+        }
+    }
+}
+
+/// Who can damage whom; indexed by the attacking
+/// faction, and then the faction of the target.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct FactionMatrix([[bool; 3]; 3]);
+
+impl Default for FactionMatrix {
+    fn default() -> Self {
+        let mut matrix = Self([[true; 3]; 3]);
+        // No friendly fire.
+        matrix.set(Faction::Player, Faction::Player, false);
+        matrix.set(Faction::Enemy, Faction::Enemy, false);
+        matrix
+    }
+}
+
+impl FactionMatrix {
+    pub fn can_damage(&self, attacker: Faction, target: Faction) -> bool {
+        self.0[attacker.index()][target.index()]
+    }
+
+    pub fn set(&mut self, attacker: Faction, target: Faction, can_damage: bool) {
+        self.0[attacker.index()][target.index()] = can_damage;
+    }
+}
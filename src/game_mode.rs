@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<GameMode>();
+    app.init_resource::<GameMode>();
+}
+
+/// How the next round is played, picked on the title screen.
+#[derive(Resource, Reflect, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[reflect(Resource)]
+pub enum GameMode {
+    #[default]
+    Solo,
+    /// Two ships on one keyboard, or two gamepads,
+    /// sharing the score and the round.
+    Coop,
+    /// Ships shooting each other for kills,
+    /// with asteroids as hazards.
+    Versus { players: u8 },
+    /// Co-op with every ship on its own instance,
+    /// connected over the network.
+    Online { players: u8 },
+}
+
+impl GameMode {
+    pub fn player_count(&self) -> u8 {
+        match self {
+            GameMode::Solo => 1,
+            GameMode::Coop => 2,
+            GameMode::Versus { players } | GameMode::Online { players } => *players,
+        }
+    }
+
+    pub fn is_versus(&self) -> bool {
+        matches!(self, GameMode::Versus { .. })
+    }
+}
+
+/// Run condition for anything hostile besides asteroids,
+/// which is left out of modes where players fight each other.
+pub fn enemies_enabled(game_mode: Res<GameMode>) -> bool {
+    !game_mode.is_versus()
+}
@@ -0,0 +1,229 @@
+use std::{collections::HashMap, fs, io};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    autopilot::AutopilotEnabled, game_mode::GameMode, round::{RoundClock, RoundMode}, run_config::RunConfig, states::GameState, stats::Score
+};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Leaderboards>();
+    app.add_systems(Startup, load_leaderboards);
+    app.add_systems(OnExit(GameState::Playing), record_round);
+}
+
+/// Where the leaderboards are kept, next to wherever the game is run from.
+const LEADERBOARDS_PATH: &str = "leaderboards.ron";
+
+/// A finished round on a leaderboard.
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct LeaderboardEntry {
+    pub score: u32,
+    pub secs: f32,
+    /// The modifiers the round was played with.
+    #[serde(default)]
+    pub modifiers: RunConfig,
+}
+
+/// The best solo rounds for each [`RoundMode`], best first.
+#[derive(Resource, Reflect, Serialize, Deserialize, Debug, Default)]
+#[reflect(Resource)]
+pub struct Leaderboards {
+    sections: HashMap<RoundMode, Vec<LeaderboardEntry>>,
+    /// The place the last round took, if it made the board.
+    #[serde(skip)]
+    latest: Option<(RoundMode, usize)>,
+}
+
+impl Leaderboards {
+    pub const LEN: usize = 5;
+
+    pub fn get(&self, mode: RoundMode) -> &[LeaderboardEntry] {
+        self.sections.get(&mode).map(Vec::as_slice).unwrap_or_default()
+    }
+
+    /// The place of the last round recorded in this `mode`,
+    /// or `None` if it didn't make the board.
+    pub fn latest(&self, mode: RoundMode) -> Option<usize> {
+        self.latest
+            .filter(|(latest_mode, _)| *latest_mode == mode)
+            .map(|(_, place)| place)
+    }
+
+    /// Add a round to its section, returning
+    /// its place if it made the board.
+    pub fn record(&mut self, mode: RoundMode, entry: LeaderboardEntry) -> Option<usize> {
+        let section = self.sections.entry(mode).or_default();
+        let place = section.partition_point(|other| !Self::beats(mode, &entry, other));
+
+        self.latest = None;
+        if place >= Self::LEN {
+            return None;
+        }
+
+        section.insert(place, entry);
+        section.truncate(Self::LEN);
+        self.latest = Some((mode, place));
+        Some(place)
+    }
+
+    /// Whether `entry` strictly beats `other`; score attack is
+    /// about time, the other modes are about score.
+    fn beats(mode: RoundMode, entry: &LeaderboardEntry, other: &LeaderboardEntry) -> bool {
+        match mode {
+            RoundMode::ScoreAttack => entry.secs < other.secs,
+            RoundMode::Classic
+            | RoundMode::TimeAttack
+            | RoundMode::DailyChallenge
+            | RoundMode::Sandbox => {
+                entry.score > other.score
+            }
+        }
+    }
+
+    fn load() -> io::Result<Self> {
+        let contents = fs::read_to_string(LEADERBOARDS_PATH)?;
+        ron::from_str(&contents).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let contents = ron::ser::to_string_pretty(self, default())
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        fs::write(LEADERBOARDS_PATH, contents)
+    }
+}
+
+fn load_leaderboards(mut commands: Commands) {
+    let leaderboards = match Leaderboards::load() {
+        Ok(leaderboards) => leaderboards,
+        // No leaderboards yet is just a first run.
+        Err(error) if error.kind() == io::ErrorKind::NotFound => default(),
+        Err(error) => {
+            warn!("Couldn't load the leaderboards: {error}");
+            default()
+        }
+    };
+    commands.insert_resource(leaderboards);
+}
+
+/// Put the round that just ended on its leaderboard.
+fn record_round(
+    mut leaderboards: ResMut<Leaderboards>,
+    round_mode: Res<RoundMode>,
+    game_mode: Res<GameMode>,
+    clock: Res<RoundClock>,
+    score: Res<Score>,
+    run_config: Res<RunConfig>,
+    autopilot_enabled: Res<AutopilotEnabled>,
+) {
+    leaderboards.latest = None;
+
+    // The boards are for one player; co-op and online scores are
+    // shared, versus has none to rank, and daily challenges are
+    // only ranked against the day.
+    if *game_mode != GameMode::Solo || *round_mode == RoundMode::DailyChallenge {
+        return;
+    }
+    // Neither zen, the sandbox, nor the autopilot are real attempts.
+    if !run_config.is_ranked() || *round_mode == RoundMode::Sandbox || autopilot_enabled.0 {
+        return;
+    }
+    // A score attack only counts if the target was reached.
+    if *round_mode == RoundMode::ScoreAttack && !clock.completed() {
+        return;
+    }
+
+    let entry = LeaderboardEntry {
+        score: score.0,
+        secs: clock.elapsed_secs(),
+        modifiers: *run_config,
+    };
+    if leaderboards.record(*round_mode, entry).is_none() {
+        return;
+    }
+
+    if let Err(error) = leaderboards.save() {
+        warn!("Couldn't save the leaderboards: {error}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(score: u32, secs: f32) -> LeaderboardEntry {
+        LeaderboardEntry {
+            score,
+            secs,
+            modifiers: RunConfig::default(),
+        }
+    }
+
+    #[test]
+    fn higher_scores_rank_first() {
+        let mut leaderboards = Leaderboards::default();
+        leaderboards.record(RoundMode::Classic, entry(10, 60.));
+        leaderboards.record(RoundMode::Classic, entry(30, 60.));
+
+        assert_eq!(leaderboards.record(RoundMode::Classic, entry(20, 60.)), Some(1));
+        assert_eq!(
+            leaderboards.get(RoundMode::Classic),
+            [entry(30, 60.), entry(20, 60.), entry(10, 60.)]
+        );
+        assert_eq!(leaderboards.latest(RoundMode::Classic), Some(1));
+    }
+
+    #[test]
+    fn ties_go_below_the_earlier_entry() {
+        let mut leaderboards = Leaderboards::default();
+        leaderboards.record(RoundMode::Classic, entry(20, 30.));
+
+        assert_eq!(leaderboards.record(RoundMode::Classic, entry(20, 90.)), Some(1));
+        assert_eq!(
+            leaderboards.get(RoundMode::Classic),
+            [entry(20, 30.), entry(20, 90.)]
+        );
+    }
+
+    #[test]
+    fn score_attack_ranks_faster_times_first() {
+        let mut leaderboards = Leaderboards::default();
+        leaderboards.record(RoundMode::ScoreAttack, entry(100, 90.));
+        leaderboards.record(RoundMode::ScoreAttack, entry(100, 120.));
+
+        // A higher score doesn't help, only the time counts.
+        assert_eq!(leaderboards.record(RoundMode::ScoreAttack, entry(150, 100.)), Some(1));
+        assert_eq!(leaderboards.record(RoundMode::ScoreAttack, entry(100, 60.)), Some(0));
+        assert_eq!(
+            leaderboards.get(RoundMode::ScoreAttack),
+            [entry(100, 60.), entry(100, 90.), entry(150, 100.), entry(100, 120.)]
+        );
+    }
+
+    #[test]
+    fn full_board_keeps_the_best() {
+        let mut leaderboards = Leaderboards::default();
+        for score in 1..=Leaderboards::LEN as u32 {
+            leaderboards.record(RoundMode::TimeAttack, entry(score * 10, 180.));
+        }
+
+        assert_eq!(leaderboards.record(RoundMode::TimeAttack, entry(10, 180.)), None);
+        assert_eq!(leaderboards.latest(RoundMode::TimeAttack), None);
+
+        assert_eq!(leaderboards.record(RoundMode::TimeAttack, entry(15, 180.)), Some(4));
+        let section = leaderboards.get(RoundMode::TimeAttack);
+        assert_eq!(section.len(), Leaderboards::LEN);
+        assert_eq!(section[Leaderboards::LEN - 1], entry(15, 180.));
+    }
+
+    #[test]
+    fn modes_are_ranked_separately() {
+        let mut leaderboards = Leaderboards::default();
+        leaderboards.record(RoundMode::Classic, entry(50, 60.));
+
+        assert_eq!(leaderboards.record(RoundMode::TimeAttack, entry(1, 180.)), Some(0));
+        assert_eq!(leaderboards.latest(RoundMode::Classic), None);
+        assert_eq!(leaderboards.get(RoundMode::Classic), [entry(50, 60.)]);
+    }
+}
@@ -2,13 +2,30 @@ use bevy::prelude::*;
 use leafwing_input_manager::prelude::*;
 
 pub mod asteroid;
+pub mod attract;
+pub mod autopilot;
+pub mod daily;
 pub mod destruction;
-pub mod health_pickup;
+pub mod enemy;
+pub mod explosion;
+pub mod faction;
+pub mod game_mode;
+pub mod leaderboard;
+pub mod magnetism;
+pub mod network;
+pub mod pickup;
 pub mod player;
+pub mod power_up;
 pub mod projectile;
+pub mod random;
+pub mod round;
+pub mod run_config;
+pub mod sandbox;
 pub mod stats;
 pub mod states;
+pub mod versus;
 pub mod viewport_bound;
+pub mod wave;
 
 pub const VIEWPORT_WIDTH: f32 = 1280.;
 pub const VIEWPORT_HEIGHT: f32 = 720.;
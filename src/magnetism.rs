@@ -0,0 +1,77 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<MagneticField>();
+    app.register_type::<Attracted>();
+    app.add_systems(FixedUpdate, magnetic_attraction);
+    app.add_systems(Update, visualize_magnetic_fields);
+}
+
+/// Pulls every [`Attracted`] entity within `radius` towards it.
+///
+/// The pull is at its full `strength` at the centre,
+/// and falls off linearly to nothing at the edge.
+#[derive(Component, Reflect, Debug, Clone, Copy)]
+#[reflect(Component)]
+pub struct MagneticField {
+    pub radius: f32,
+    pub strength: f32,
+}
+
+/// Marks an entity as being pulled by [`MagneticField`]s;
+/// the pull is multiplied by the contained value.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Attracted(pub f32);
+
+fn magnetic_attraction(
+    field_query: Query<(&MagneticField, &Transform)>,
+    mut attracted_query: Query<(&Attracted, &Transform, &mut LinearVelocity)>,
+    time: Res<Time<Fixed>>,
+) {
+    for (attracted, transform, mut linear_velocity) in &mut attracted_query {
+        let position = transform.translation.xy();
+
+        for (field, field_transform) in &field_query {
+            let offset = field_transform.translation.xy() - position;
+            let distance = offset.length();
+            if distance > field.radius {
+                continue;
+            }
+
+            let falloff = 1.0 - distance / field.radius;
+            linear_velocity.0 += offset.normalize_or_zero()
+                * field.strength
+                * falloff
+                * attracted.0
+                * time.delta_seconds();
+        }
+    }
+}
+
+/// Use gizmos to render a faint ring around the edge of the
+/// field, and a pulsing ring inside of it whose brightness
+/// represents the field's strength.
+fn visualize_magnetic_fields(
+    mut gizmos: Gizmos,
+    query: Query<(&MagneticField, &Transform)>,
+    time: Res<Time>,
+) {
+    const PULSE_SECS: f32 = 1.5;
+    const MAX_STRENGTH_ALPHA: f32 = 500.;
+
+    let pulse = 1.0 - (time.elapsed_seconds() / PULSE_SECS).fract();
+
+    for (field, transform) in &query {
+        let position = transform.translation.xy();
+        let alpha = (field.strength / MAX_STRENGTH_ALPHA).clamp(0.1, 1.0);
+
+        gizmos.circle_2d(position, field.radius, Color::srgba(0.4, 0.6, 1.0, 0.15));
+        gizmos.circle_2d(
+            position,
+            field.radius * pulse,
+            Color::srgba(0.4, 0.6, 1.0, 0.3 * alpha * (1.0 - pulse)),
+        );
+    }
+}
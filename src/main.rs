@@ -1,16 +1,24 @@
+use std::time::Duration;
+
 use avian2d::prelude::*;
-use bevy::{prelude::*, render::camera::ScalingMode};
+use bevy::{
+    app::ScheduleRunnerPlugin, prelude::*, render::{camera::ScalingMode, settings::WgpuSettings, RenderPlugin}, time::TimeUpdateStrategy, window::ExitCondition, winit::WinitPlugin
+};
 use bevy_asset_loader::prelude::*;
 use bevy_transform_interpolation::*;
 use leafwing_input_manager::prelude::*;
 
 use game_library::{
-    asteroid, destruction, health_pickup, player, projectile, states::{self, GameState}, stats, viewport_bound, Action, VIEWPORT_HEIGHT, VIEWPORT_WIDTH
+    asteroid, attract, autopilot, daily, destruction, enemy, explosion, faction, game_mode, leaderboard, magnetism, network, pickup, player, power_up, projectile, random, round, run_config, sandbox, states::{self, GameState}, stats, versus, viewport_bound, wave, Action, VIEWPORT_HEIGHT, VIEWPORT_WIDTH
 };
 use sickle_ui::SickleUiPlugin;
 
 
 fn main() {
+    // Soak tests run without a window, or rendering,
+    // as fast as they can.
+    let headless = std::env::args().any(|arg| arg == "--headless");
+
     let window_configuration = WindowPlugin {
         primary_window: Some(Window {
             title: "Asteroids Clone".to_string(),
@@ -20,9 +28,37 @@ fn main() {
     };
 
     let mut app = App::new();
-    app.add_plugins(DefaultPlugins.set(window_configuration));
+    if headless {
+        app.add_plugins((
+            DefaultPlugins
+                .set(WindowPlugin {
+                    primary_window: None,
+                    exit_condition: ExitCondition::DontExit,
+                    ..default()
+                })
+                .set(RenderPlugin {
+                    render_creation: WgpuSettings {
+                        backends: None,
+                        ..default()
+                    }
+                    .into(),
+                    ..default()
+                })
+                .disable::<WinitPlugin>(),
+            ScheduleRunnerPlugin::run_loop(Duration::ZERO),
+        ));
+        // Every frame steps time by exactly one fixed tick, so the
+        // simulation runs as it would live, just faster, rather
+        // than having to catch up on however long a frame took.
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(
+            Time::<Fixed>::default().timestep(),
+        ));
+    } else {
+        app.add_plugins(DefaultPlugins.set(window_configuration));
+    }
     app.add_plugins(states::plugin);
     app.add_plugins(PhysicsPlugins::default());
+    app.insert_resource(Gravity::ZERO);
     app.add_plugins(TransformInterpolationPlugin::default());
     app.add_plugins(InputManagerPlugin::<Action>::default());
     app.add_plugins(SickleUiPlugin);
@@ -32,12 +68,29 @@ fn main() {
             .with_dynamic_assets_file::<StandardDynamicAssetCollection>("game.assets.ron"),
     );
     app.add_plugins(asteroid::plugin);
+    app.add_plugins(attract::plugin);
+    app.add_plugins(autopilot::plugin);
+    app.add_plugins(daily::plugin);
     app.add_plugins(destruction::plugin);
-    app.add_plugins(health_pickup::plugin);
+    app.add_plugins(enemy::plugin);
+    app.add_plugins(explosion::plugin);
+    app.add_plugins(faction::plugin);
+    app.add_plugins(game_mode::plugin);
+    app.add_plugins(leaderboard::plugin);
+    app.add_plugins(magnetism::plugin);
+    app.add_plugins(network::plugin);
+    app.add_plugins(pickup::plugin);
     app.add_plugins(player::plugin);
+    app.add_plugins(power_up::plugin);
     app.add_plugins(projectile::plugin);
+    app.add_plugins(random::plugin);
+    app.add_plugins(round::plugin);
+    app.add_plugins(run_config::plugin);
+    app.add_plugins(sandbox::plugin);
     app.add_plugins(stats::plugin);
+    app.add_plugins(versus::plugin);
     app.add_plugins(viewport_bound::plugin);
+    app.add_plugins(wave::plugin);
     app.add_systems(Startup, setup_camera);
     app.insert_resource(ClearColor(Color::srgb(0., 0., 0.)));
 
@@ -45,7 +98,10 @@ fn main() {
     #[cfg(feature = "dev")]
     {
         app.add_plugins(PhysicsDebugPlugin::default());
-        app.add_plugins(bevy_inspector_egui::quick::WorldInspectorPlugin::new());
+        // The inspector needs a window to draw in.
+        if !headless {
+            app.add_plugins(bevy_inspector_egui::quick::WorldInspectorPlugin::new());
+        }
     }
 
     app.run();
@@ -0,0 +1,855 @@
+//! Online play, exchanging every ship's inputs
+//! for each fixed tick over UDP.
+//!
+//! Each instance sends the inputs of its own ship to every peer,
+//! a few ticks before they're played, and plays every ship, its
+//! own included, from those inputs. A tick isn't played until
+//! every peer's input for it has arrived, so the game stalls
+//! rather than guess; with enough input delay to cover the lag,
+//! the inputs arrive in time and the stall isn't noticed.
+//!
+//! This keeps the ships' inputs in lockstep, but it isn't rollback:
+//! nothing is predicted, so nothing is ever resimulated. Shooting,
+//! collisions and pickups still run in `Update`, on each instance's
+//! own frame rate, so instances can drift apart. Every packet carries
+//! a checksum of where the ships were on the tick it was sent, and
+//! the first one that doesn't match is reported as a desync.
+//!
+//! A round only starts once every instance has joined it, so that
+//! their ticks line up. A peer that goes quiet for too long during
+//! a round is given up on, and its ship keeps its last input.
+//!
+//! A session is set up from the command line, for example, for
+//! two instances on one machine, with simulated lag and loss:
+//!
+//! ```text
+//! game_binary --net-player 0 --net-bind 127.0.0.1:7000 --net-peer 1@127.0.0.1:7001 --net-lag 80 --net-loss 0.05
+//! game_binary --net-player 1 --net-bind 127.0.0.1:7001 --net-peer 0@127.0.0.1:7000 --net-lag 80 --net-loss 0.05
+//! ```
+//!
+//! `--net-delay` sets how many ticks inputs are held back by;
+//! the more lag there is, the more it takes to avoid stalls.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    io,
+    net::{SocketAddr, UdpSocket},
+    time::{Duration, Instant},
+};
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+
+use crate::{
+    player::{Player, PlayerNumber}, random::FixedSeed, states::GameState, Action
+};
+
+pub mod link_conditioner;
+
+use link_conditioner::LinkConditioner;
+
+pub fn plugin(app: &mut App) {
+    let Some(config) = NetworkConfig::from_args(std::env::args().skip(1)) else {
+        return;
+    };
+
+    let seed = config.seed;
+    match NetworkSession::bind(config) {
+        Ok(session) => {
+            app.insert_resource(session);
+            app.insert_resource(FixedSeed(seed));
+        }
+        Err(error) => {
+            error!("Couldn't start the network session: {error}");
+            return;
+        }
+    }
+
+    app.add_systems(OnEnter(GameState::Playing), start_network_round);
+    app.add_systems(OnExit(GameState::Playing), resume_time);
+    app.add_systems(
+        Update,
+        wait_for_peers.run_if(in_state(GameState::Playing).and_then(not(network_round_started))),
+    );
+    app.add_systems(
+        FixedPreUpdate,
+        (send_local_input, wait_for_remote_inputs, apply_inputs)
+            .chain()
+            .run_if(in_state(GameState::Playing).and_then(network_round_started)),
+    );
+    app.add_systems(
+        FixedPostUpdate,
+        advance_network_tick.run_if(in_state(GameState::Playing).and_then(network_round_started)),
+    );
+}
+
+/// The most players a session can have.
+pub const MAX_PLAYERS: u8 = 4;
+
+/// How many ticks of inputs and checksums are kept.
+const MAX_HISTORY_TICKS: u32 = 128;
+
+/// How a session is set up, read from the command line.
+#[derive(Debug)]
+struct NetworkConfig {
+    local_player: u8,
+    bind: SocketAddr,
+    peers: Vec<(u8, SocketAddr)>,
+    /// Every instance must use the same seed,
+    /// so that they spawn the same asteroids.
+    seed: u64,
+    /// How many ticks after being pressed an input is played.
+    input_delay: u32,
+    link_conditioner: LinkConditioner,
+}
+
+impl NetworkConfig {
+    /// Enough to cover a round trip of about 50ms
+    /// at the default fixed rate of 64 ticks a second.
+    const DEFAULT_INPUT_DELAY: u32 = 3;
+
+    /// Returns `None` if no session was asked for, and
+    /// logs the problem if it was asked for incorrectly.
+    fn from_args(mut args: impl Iterator<Item = String>) -> Option<Self> {
+        let mut local_player = None;
+        let mut bind = None;
+        let mut peers = vec![];
+        let mut seed = 0;
+        let mut input_delay = Self::DEFAULT_INPUT_DELAY;
+        let mut lag = Duration::ZERO;
+        let mut jitter = Duration::ZERO;
+        let mut loss = 0.;
+
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--net-") {
+                continue;
+            }
+
+            let Some(value) = args.next() else {
+                error!("Missing a value for {arg}");
+                return None;
+            };
+
+            let parsed = match arg.as_str() {
+                "--net-player" => value.parse().map(|player| local_player = Some(player)).ok(),
+                "--net-bind" => value.parse().map(|address| bind = Some(address)).ok(),
+                "--net-peer" => value.split_once('@').and_then(|(player, address)| {
+                    peers.push((player.parse().ok()?, address.parse().ok()?));
+                    Some(())
+                }),
+                "--net-seed" => value.parse().map(|value| seed = value).ok(),
+                "--net-delay" => value.parse().map(|ticks| input_delay = ticks).ok(),
+                "--net-lag" => value.parse().map(|millis| lag = Duration::from_millis(millis)).ok(),
+                "--net-jitter" => value
+                    .parse()
+                    .map(|millis| jitter = Duration::from_millis(millis))
+                    .ok(),
+                "--net-loss" => value.parse().map(|value| loss = value).ok(),
+                _ => None,
+            };
+
+            if parsed.is_none() {
+                error!("Couldn't use {value} for {arg}");
+                return None;
+            }
+        }
+
+        let (Some(local_player), Some(bind)) = (local_player, bind) else {
+            if !peers.is_empty() {
+                error!("A network session needs both --net-player and --net-bind");
+            }
+            return None;
+        };
+
+        if peers.is_empty() || peers.len() >= MAX_PLAYERS as usize {
+            error!("A network session needs between 1 and {} peers", MAX_PLAYERS - 1);
+            return None;
+        }
+
+        // Inputs further ahead than this wouldn't all fit in one packet.
+        if input_delay >= NetworkSession::REDUNDANT_TICKS {
+            error!(
+                "--net-delay needs to be less than {} ticks",
+                NetworkSession::REDUNDANT_TICKS
+            );
+            return None;
+        }
+
+        Some(Self {
+            local_player,
+            bind,
+            peers,
+            seed,
+            input_delay,
+            link_conditioner: LinkConditioner::new(lag, jitter, loss),
+        })
+    }
+}
+
+/// A snapshot of one ship's [`ActionState<Action>`] for one tick.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct TickInput {
+    /// The rotate axis, scaled to fit in a byte.
+    rotate: i8,
+    moving: bool,
+    shooting: bool,
+}
+
+impl TickInput {
+    const MOVE_BIT: u8 = 1;
+    const SHOOT_BIT: u8 = 1 << 1;
+
+    fn from_action_state(action_state: &ActionState<Action>) -> Self {
+        Self {
+            rotate: (action_state.clamped_value(&Action::Rotate) * i8::MAX as f32) as i8,
+            moving: action_state.pressed(&Action::Move),
+            shooting: action_state.pressed(&Action::Shoot),
+        }
+    }
+
+    fn apply(&self, action_state: &mut ActionState<Action>) {
+        action_state.set_value(&Action::Rotate, self.rotate as f32 / i8::MAX as f32);
+
+        for (action, pressed) in [(Action::Move, self.moving), (Action::Shoot, self.shooting)] {
+            if pressed {
+                action_state.press(&action);
+            } else {
+                action_state.release(&action);
+            }
+        }
+    }
+
+    fn encode(&self) -> [u8; 2] {
+        let mut buttons = 0;
+        if self.moving {
+            buttons |= Self::MOVE_BIT;
+        }
+        if self.shooting {
+            buttons |= Self::SHOOT_BIT;
+        }
+
+        [self.rotate as u8, buttons]
+    }
+
+    fn decode([rotate, buttons]: [u8; 2]) -> Self {
+        Self {
+            rotate: rotate as i8,
+            moving: buttons & Self::MOVE_BIT != 0,
+            shooting: buttons & Self::SHOOT_BIT != 0,
+        }
+    }
+}
+
+/// A packet carrying a player's inputs for the latest
+/// few ticks, so that a lost packet is covered by the next,
+/// along with the sender's checksum of the ships on one tick.
+///
+/// Laid out as the player's number, then the round, the latest
+/// tick and the checksum's tick as little endian `u32`s, the
+/// checksum as a little endian `u64`, the number of inputs, and
+/// then each input, oldest first. A packet without any inputs
+/// just says which round its sender is in, and has no checksum.
+#[derive(Debug, PartialEq)]
+struct InputPacket {
+    player: u8,
+    round: u32,
+    latest_tick: u32,
+    checksum_tick: u32,
+    checksum: u64,
+    inputs: Vec<TickInput>,
+}
+
+impl InputPacket {
+    const HEADER_LEN: usize = 22;
+
+    fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(Self::HEADER_LEN + self.inputs.len() * 2);
+        bytes.push(self.player);
+        bytes.extend_from_slice(&self.round.to_le_bytes());
+        bytes.extend_from_slice(&self.latest_tick.to_le_bytes());
+        bytes.extend_from_slice(&self.checksum_tick.to_le_bytes());
+        bytes.extend_from_slice(&self.checksum.to_le_bytes());
+        bytes.push(self.inputs.len() as u8);
+        for input in &self.inputs {
+            bytes.extend_from_slice(&input.encode());
+        }
+        bytes
+    }
+
+    fn decode(bytes: &[u8]) -> Option<Self> {
+        let header = bytes.get(..Self::HEADER_LEN)?;
+        let count = header[21] as usize;
+        let body = bytes.get(Self::HEADER_LEN..Self::HEADER_LEN + count * 2)?;
+
+        Some(Self {
+            player: header[0],
+            round: u32::from_le_bytes(header[1..5].try_into().ok()?),
+            latest_tick: u32::from_le_bytes(header[5..9].try_into().ok()?),
+            checksum_tick: u32::from_le_bytes(header[9..13].try_into().ok()?),
+            checksum: u64::from_le_bytes(header[13..21].try_into().ok()?),
+            inputs: body
+                .chunks_exact(2)
+                .map(|chunk| TickInput::decode([chunk[0], chunk[1]]))
+                .collect(),
+        })
+    }
+}
+
+/// The inputs one player has sent for the latest ticks.
+#[derive(Debug, Default)]
+struct PlayerInputs(BTreeMap<u32, TickInput>);
+
+impl PlayerInputs {
+    fn insert(&mut self, tick: u32, input: TickInput) {
+        self.0.insert(tick, input);
+        self.0 = self.0.split_off(&tick.saturating_sub(MAX_HISTORY_TICKS));
+    }
+
+    fn contains(&self, tick: u32) -> bool {
+        self.0.contains_key(&tick)
+    }
+
+    /// The input for `tick`, or the latest one before
+    /// it, for a player that has stopped sending them.
+    fn get_or_last(&self, tick: u32) -> TickInput {
+        self.0
+            .range(..=tick)
+            .next_back()
+            .map(|(_, input)| *input)
+            .unwrap_or_default()
+    }
+}
+
+/// Each player's checksum of the ships, by tick.
+#[derive(Debug, Default)]
+struct Checksums(BTreeMap<u32, BTreeMap<u8, u64>>);
+
+impl Checksums {
+    /// Returns a player that had a different
+    /// checksum for the same tick, if there is one.
+    fn insert(&mut self, tick: u32, player: u8, checksum: u64) -> Option<u8> {
+        let tick_checksums = self.0.entry(tick).or_default();
+        let disagreeing = tick_checksums
+            .iter()
+            .find(|(_, other)| **other != checksum)
+            .map(|(other_player, _)| *other_player);
+        tick_checksums.insert(player, checksum);
+
+        self.0 = self.0.split_off(&tick.saturating_sub(MAX_HISTORY_TICKS));
+        disagreeing
+    }
+}
+
+/// FNV-1a over the bits of each value, which, unlike the standard
+/// library's hasher, is sure to give the same result on every build.
+fn checksum(values: impl IntoIterator<Item = f32>) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x100_0000_01b3;
+
+    values
+        .into_iter()
+        .flat_map(f32::to_le_bytes)
+        .fold(OFFSET_BASIS, |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(PRIME)
+        })
+}
+
+/// Marks a ship as being controlled from another instance.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct RemotePlayer;
+
+/// The keyboard and gamepad of this instance's own ship,
+/// which are sent on rather than played straight away.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct LocalInput;
+
+#[derive(Resource)]
+pub struct NetworkSession {
+    config: NetworkConfig,
+    socket: UdpSocket,
+    /// Counts up with every round played, so that
+    /// packets left over from earlier ones are ignored.
+    round: u32,
+    /// Whether every peer has joined this round yet.
+    started: bool,
+    /// The latest round each peer has been heard from in.
+    peer_rounds: BTreeMap<u8, u32>,
+    /// Peers that stopped sending inputs this round.
+    disconnected: BTreeSet<u8>,
+    tick: u32,
+    inputs: BTreeMap<u8, PlayerInputs>,
+    checksums: Checksums,
+    /// Whether a desync has been reported this round,
+    /// as every later checksum will differ too.
+    desynced: bool,
+    /// The latest packet sent, to be sent again
+    /// while waiting on the peers, in case it was lost.
+    last_packet: Vec<u8>,
+}
+
+impl NetworkSession {
+    /// How many of the latest ticks are sent in each packet.
+    const REDUNDANT_TICKS: u32 = 8;
+
+    fn bind(config: NetworkConfig) -> io::Result<Self> {
+        let socket = UdpSocket::bind(config.bind)?;
+        socket.set_nonblocking(true)?;
+
+        Ok(Self {
+            config,
+            socket,
+            round: 0,
+            started: false,
+            peer_rounds: BTreeMap::new(),
+            disconnected: BTreeSet::new(),
+            tick: 0,
+            inputs: BTreeMap::new(),
+            checksums: Checksums::default(),
+            desynced: false,
+            last_packet: vec![],
+        })
+    }
+
+    pub fn player_count(&self) -> u8 {
+        self.config.peers.len() as u8 + 1
+    }
+
+    pub fn is_local(&self, player: PlayerNumber) -> bool {
+        player.0 == self.config.local_player
+    }
+
+    fn send(&mut self, packet: &InputPacket) {
+        self.last_packet = packet.encode();
+        self.resend();
+    }
+
+    fn resend(&mut self) {
+        for (_, address) in &self.config.peers {
+            self.config
+                .link_conditioner
+                .send(self.last_packet.clone(), *address);
+        }
+
+        self.flush();
+    }
+
+    /// Send on any packets that have waited out their simulated lag.
+    fn flush(&mut self) {
+        if let Err(error) = self.config.link_conditioner.flush(&self.socket) {
+            warn!("Couldn't send inputs: {error}");
+        }
+    }
+
+    /// Read every packet that has arrived, keeping the
+    /// inputs and checksums of any that are for this round.
+    fn receive(&mut self) {
+        const MAX_PACKET_LEN: usize = 512;
+
+        let mut buffer = [0; MAX_PACKET_LEN];
+
+        loop {
+            let (len, address) = match self.socket.recv_from(&mut buffer) {
+                Ok(received) => received,
+                Err(error) if error.kind() == io::ErrorKind::WouldBlock => break,
+                // Sending to a peer that isn't listening can surface here on some platforms.
+                Err(error)
+                    if matches!(
+                        error.kind(),
+                        io::ErrorKind::ConnectionReset | io::ErrorKind::ConnectionRefused
+                    ) =>
+                {
+                    continue
+                }
+                Err(error) => {
+                    warn!("Couldn't receive inputs: {error}");
+                    break;
+                }
+            };
+
+            let Some(packet) = InputPacket::decode(&buffer[..len]) else {
+                continue;
+            };
+
+            // Only trust a peer to send its own inputs.
+            if !self
+                .config
+                .peers
+                .iter()
+                .any(|(player, peer_address)| *player == packet.player && *peer_address == address)
+            {
+                continue;
+            }
+
+            let peer_round = self.peer_rounds.entry(packet.player).or_default();
+            *peer_round = (*peer_round).max(packet.round);
+
+            if packet.round != self.round || packet.inputs.is_empty() {
+                continue;
+            }
+
+            let inputs = self.inputs.entry(packet.player).or_default();
+            let first_tick = packet
+                .latest_tick
+                .saturating_sub(packet.inputs.len().saturating_sub(1) as u32);
+            for (tick, input) in (first_tick..).zip(packet.inputs) {
+                inputs.insert(tick, input);
+            }
+
+            self.record_checksum(packet.player, packet.checksum_tick, packet.checksum);
+        }
+    }
+
+    /// Keep a player's checksum, reporting the
+    /// first one this round that doesn't match.
+    fn record_checksum(&mut self, player: u8, tick: u32, checksum: u64) {
+        let Some(other_player) = self.checksums.insert(tick, player, checksum) else {
+            return;
+        };
+
+        if !self.desynced {
+            self.desynced = true;
+            error!(
+                "Players {} and {} are out of sync as of tick {tick}",
+                player + 1,
+                other_player + 1
+            );
+        }
+    }
+
+    fn all_peers_joined(&self) -> bool {
+        self.config.peers.iter().all(|(player, _)| {
+            self.peer_rounds
+                .get(player)
+                .is_some_and(|round| *round >= self.round)
+        })
+    }
+
+    /// The peers still playing whose input for this tick hasn't arrived.
+    fn missing_peers(&self) -> Vec<u8> {
+        self.config
+            .peers
+            .iter()
+            .map(|(player, _)| *player)
+            .filter(|player| !self.disconnected.contains(player))
+            .filter(|player| {
+                !self
+                    .inputs
+                    .get(player)
+                    .is_some_and(|inputs| inputs.contains(self.tick))
+            })
+            .collect()
+    }
+}
+
+fn network_round_started(session: Res<NetworkSession>) -> bool {
+    session.started
+}
+
+/// Every instance plays the round from the same seed, through
+/// the [`FixedSeed`], but time is held until they've all joined.
+fn start_network_round(mut session: ResMut<NetworkSession>, mut time: ResMut<Time<Virtual>>) {
+    let session = &mut *session;
+
+    session.round += 1;
+    session.started = false;
+    session.disconnected.clear();
+    session.tick = 0;
+    session.inputs.clear();
+    session.checksums = Checksums::default();
+    session.desynced = false;
+
+    // No input can be sent early enough for the first few
+    // ticks, so every instance plays them with none.
+    let local_inputs = session
+        .inputs
+        .entry(session.config.local_player)
+        .or_default();
+    for tick in 0..session.config.input_delay {
+        local_inputs.insert(tick, TickInput::default());
+    }
+
+    time.pause();
+}
+
+fn resume_time(mut time: ResMut<Time<Virtual>>) {
+    time.unpause();
+}
+
+/// Tell the peers which round this instance is in, until they
+/// have all said the same, then start the round together.
+///
+/// A peer that has already started shows it by sending inputs
+/// for the round, which count the same.
+fn wait_for_peers(mut session: ResMut<NetworkSession>, mut time: ResMut<Time<Virtual>>) {
+    session.receive();
+
+    let packet = InputPacket {
+        player: session.config.local_player,
+        round: session.round,
+        latest_tick: 0,
+        checksum_tick: 0,
+        checksum: 0,
+        inputs: vec![],
+    };
+    session.send(&packet);
+
+    if session.all_peers_joined() {
+        session.started = true;
+        time.unpause();
+    }
+}
+
+/// Record this instance's own input to be played after the input
+/// delay, and send it, along with the few before it and a checksum
+/// of where the ships are at the start of this tick.
+///
+/// Once the ship is gone, an empty input is sent in its
+/// place, as the peers still need one for every tick.
+fn send_local_input(
+    mut session: ResMut<NetworkSession>,
+    local_input: Query<&ActionState<Action>, With<LocalInput>>,
+    ships: Query<(&PlayerNumber, &Transform, &LinearVelocity, &AngularVelocity), With<Player>>,
+) {
+    let session = &mut *session;
+
+    let mut ships: Vec<_> = ships.iter().collect();
+    ships.sort_by_key(|(number, ..)| number.0);
+    let ships_checksum = checksum(ships.into_iter().flat_map(
+        |(number, transform, linear_velocity, angular_velocity)| {
+            [
+                number.0 as f32,
+                transform.translation.x,
+                transform.translation.y,
+                transform.rotation.z,
+                transform.rotation.w,
+                linear_velocity.0.x,
+                linear_velocity.0.y,
+                angular_velocity.0,
+            ]
+        },
+    ));
+
+    let local_player = session.config.local_player;
+    let tick = session.tick;
+    session.record_checksum(local_player, tick, ships_checksum);
+
+    let latest_tick = tick + session.config.input_delay;
+    let input = local_input
+        .get_single()
+        .map(TickInput::from_action_state)
+        .unwrap_or_default();
+
+    let inputs = session.inputs.entry(local_player).or_default();
+    inputs.insert(latest_tick, input);
+
+    let first_tick = latest_tick.saturating_sub(NetworkSession::REDUNDANT_TICKS - 1);
+    let packet = InputPacket {
+        player: local_player,
+        round: session.round,
+        latest_tick,
+        checksum_tick: tick,
+        checksum: ships_checksum,
+        inputs: inputs
+            .0
+            .range(first_tick..=latest_tick)
+            .map(|(_, input)| *input)
+            .collect(),
+    };
+    session.send(&packet);
+}
+
+/// Hold the tick, and with it the whole game, until every peer's
+/// input for it has arrived, sending this instance's latest packet
+/// again now and then in case that's what was lost.
+///
+/// A peer that still hasn't sent one after a while is taken to have
+/// left, and isn't waited for again this round.
+fn wait_for_remote_inputs(mut session: ResMut<NetworkSession>) {
+    const POLL_INTERVAL: Duration = Duration::from_millis(1);
+    const RESEND_INTERVAL: Duration = Duration::from_millis(15);
+    const TIMEOUT: Duration = Duration::from_secs(3);
+
+    let waiting_since = Instant::now();
+    let mut last_sent = waiting_since;
+
+    loop {
+        session.receive();
+
+        let missing = session.missing_peers();
+        if missing.is_empty() {
+            return;
+        }
+
+        if waiting_since.elapsed() >= TIMEOUT {
+            for player in missing {
+                warn!(
+                    "Lost player {}, whose ship will carry on with its last input",
+                    player + 1
+                );
+                session.disconnected.insert(player);
+            }
+            return;
+        }
+
+        if last_sent.elapsed() >= RESEND_INTERVAL {
+            session.resend();
+            last_sent = Instant::now();
+        } else {
+            session.flush();
+        }
+
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Play this tick's inputs on every ship.
+fn apply_inputs(
+    session: Res<NetworkSession>,
+    mut query: Query<(&PlayerNumber, &mut ActionState<Action>), With<Player>>,
+) {
+    for (number, mut action_state) in &mut query {
+        session
+            .inputs
+            .get(&number.0)
+            .map(|inputs| inputs.get_or_last(session.tick))
+            .unwrap_or_default()
+            .apply(&mut action_state);
+    }
+}
+
+fn advance_network_tick(mut session: ResMut<NetworkSession>) {
+    session.tick += 1;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tick_input_round_trips() {
+        for input in [
+            TickInput::default(),
+            TickInput {
+                rotate: i8::MAX,
+                moving: true,
+                shooting: false,
+            },
+            TickInput {
+                rotate: -1,
+                moving: false,
+                shooting: true,
+            },
+            TickInput {
+                rotate: -i8::MAX,
+                moving: true,
+                shooting: true,
+            },
+        ] {
+            assert_eq!(TickInput::decode(input.encode()), input);
+        }
+    }
+
+    #[test]
+    fn negative_rotate_is_sent_as_its_twos_complement() {
+        let input = TickInput {
+            rotate: -127,
+            moving: false,
+            shooting: false,
+        };
+
+        assert_eq!(input.encode(), [0x81, 0]);
+        assert_eq!(TickInput::decode([0xff, 0]).rotate, -1);
+    }
+
+    #[test]
+    fn input_packet_round_trips() {
+        let packet = InputPacket {
+            player: 2,
+            round: 3,
+            latest_tick: 70_000,
+            checksum_tick: 69_997,
+            checksum: 0x0123_4567_89ab_cdef,
+            inputs: vec![
+                TickInput {
+                    rotate: -64,
+                    moving: true,
+                    shooting: false,
+                },
+                TickInput::default(),
+            ],
+        };
+
+        assert_eq!(InputPacket::decode(&packet.encode()), Some(packet));
+    }
+
+    #[test]
+    fn truncated_input_packet_is_rejected() {
+        let bytes = InputPacket {
+            player: 1,
+            round: 1,
+            latest_tick: 9,
+            checksum_tick: 6,
+            checksum: 1,
+            inputs: vec![TickInput::default(); 3],
+        }
+        .encode();
+
+        for len in 0..bytes.len() {
+            assert_eq!(InputPacket::decode(&bytes[..len]), None, "{len} bytes");
+        }
+    }
+
+    #[test]
+    fn missing_input_falls_back_to_the_last_one() {
+        let moving = TickInput {
+            moving: true,
+            ..default()
+        };
+        let mut inputs = PlayerInputs::default();
+        assert_eq!(inputs.get_or_last(0), TickInput::default());
+
+        inputs.insert(3, moving);
+        inputs.insert(4, TickInput::default());
+
+        assert!(!inputs.contains(2));
+        assert_eq!(inputs.get_or_last(2), TickInput::default());
+        assert_eq!(inputs.get_or_last(3), moving);
+        assert_eq!(inputs.get_or_last(4), TickInput::default());
+        assert_eq!(inputs.get_or_last(9), TickInput::default());
+    }
+
+    #[test]
+    fn old_inputs_are_forgotten() {
+        let mut inputs = PlayerInputs::default();
+        inputs.insert(0, TickInput::default());
+        inputs.insert(MAX_HISTORY_TICKS + 1, TickInput::default());
+
+        assert!(!inputs.contains(0));
+        assert!(inputs.contains(MAX_HISTORY_TICKS + 1));
+    }
+
+    #[test]
+    fn mismatched_checksum_is_caught() {
+        let mut checksums = Checksums::default();
+
+        assert_eq!(checksums.insert(5, 0, 10), None);
+        assert_eq!(checksums.insert(5, 1, 10), None);
+        assert_eq!(checksums.insert(6, 1, 11), None);
+        assert_eq!(checksums.insert(6, 0, 12), Some(1));
+    }
+
+    #[test]
+    fn checksum_is_fnv_1a() {
+        assert_eq!(checksum([]), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(checksum([0.]), 0x4d25_767f_9dce_13f5);
+        assert_eq!(checksum([1.]), 0x4b72_477f_9c5c_2f98);
+    }
+
+    #[test]
+    fn checksum_depends_on_order() {
+        assert_ne!(checksum([0., 1.]), checksum([1., 0.]));
+    }
+}
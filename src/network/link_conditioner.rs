@@ -0,0 +1,70 @@
+use std::{
+    collections::VecDeque,
+    io,
+    net::{SocketAddr, UdpSocket},
+    time::{Duration, Instant},
+};
+
+use rand::{thread_rng, Rng};
+
+/// Holds back, and randomly drops, outgoing packets to
+/// simulate a real connection while testing on one machine.
+///
+/// The randomness here is deliberately not drawn from
+/// the game's seeded generator, as it isn't gameplay.
+#[derive(Debug, Default)]
+pub struct LinkConditioner {
+    pub lag: Duration,
+    /// How much the lag of each packet can vary, either way.
+    pub jitter: Duration,
+    /// The chance, from 0 to 1, of a packet being lost.
+    pub loss: f64,
+    queue: VecDeque<(Instant, SocketAddr, Vec<u8>)>,
+}
+
+impl LinkConditioner {
+    pub fn new(lag: Duration, jitter: Duration, loss: f64) -> Self {
+        Self {
+            lag,
+            jitter,
+            loss,
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Queue a packet, unless it is lost on the way.
+    pub fn send(&mut self, packet: Vec<u8>, address: SocketAddr) {
+        let mut rng = thread_rng();
+        if rng.gen_bool(self.loss.clamp(0., 1.)) {
+            return;
+        }
+
+        let jitter = self.jitter.as_secs_f32();
+        let lag = (self.lag.as_secs_f32() + rng.gen_range(-jitter..=jitter)).max(0.);
+        let send_at = Instant::now() + Duration::from_secs_f32(lag);
+
+        // Keep the queue in send order, so jitter can reorder packets.
+        let index = self.queue.partition_point(|(other, ..)| *other <= send_at);
+        self.queue.insert(index, (send_at, address, packet));
+    }
+
+    /// Actually send every packet that has waited out its lag.
+    pub fn flush(&mut self, socket: &UdpSocket) -> io::Result<()> {
+        let now = Instant::now();
+        while let Some((send_at, ..)) = self.queue.front() {
+            if *send_at > now {
+                break;
+            }
+
+            let (_, address, packet) = self.queue.pop_front().unwrap();
+            match socket.send_to(&packet, address) {
+                Ok(_) => {}
+                // The other side not listening yet is the same as the packet being lost.
+                Err(error) if error.kind() == io::ErrorKind::ConnectionRefused => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,283 @@
+use avian2d::prelude::*;
+use bevy::{
+    color::palettes::css::{AQUA, CRIMSON, GOLD, LIME, MAGENTA, MEDIUM_PURPLE, ORANGE, SILVER, WHITE},
+    prelude::*,
+    sprite::{MaterialMesh2dBundle, Mesh2dHandle},
+    utils::HashSet,
+};
+use bevy_asset_loader::prelude::*;
+use serde::Deserialize;
+
+use crate::{
+    destruction::Destroyed, player::{ExtraLives, Player}, power_up::{PowerUp, PowerUps}, states::GameState, stats::Health
+};
+
+pub fn plugin(app: &mut App) {
+    app.configure_loading_state(
+        LoadingStateConfig::new(GameState::Loading).load_collection::<PickupAssets>(),
+    );
+    app.register_type::<Pickup>();
+    app.register_type::<PickupMagnet>();
+    app.observe(spawn_pickup);
+    app.add_systems(FixedUpdate, pickup_magnet);
+    app.add_systems(
+        Update,
+        (
+            pickup_tick,
+            pickup_blink,
+            pickup_collision,
+            pickup_timeout,
+            pickup_destroyed,
+        )
+            .chain(),
+    );
+}
+
+#[derive(AssetCollection, Resource)]
+struct PickupAssets {
+    #[asset(key = "image.health_pickup")]
+    health_pickup: Handle<Image>,
+}
+
+/// What happens to the player when they collect a [`Pickup`].
+#[derive(Reflect, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum PickupEffect {
+    Heal(u16),
+    ExtraLife,
+    PowerUp { power_up: PowerUp, secs: f32 },
+}
+
+impl PickupEffect {
+    pub fn get_name(&self) -> String {
+        String::from(match self {
+            PickupEffect::Heal(_) => "HealthPickup",
+            PickupEffect::ExtraLife => "ExtraLifePickup",
+            PickupEffect::PowerUp { power_up, .. } => match power_up {
+                PowerUp::Shield => "ShieldPickup",
+                PowerUp::RapidFire => "RapidFirePickup",
+                PowerUp::Multishot(_) => "MultishotPickup",
+                PowerUp::Piercing => "PiercingPickup",
+                PowerUp::SlowMotion(_) => "SlowMotionPickup",
+                PowerUp::Magnet(_) => "MagnetPickup",
+                PowerUp::ScoreMultiplier(_) => "ScoreMultiplierPickup",
+            },
+        })
+    }
+
+    fn get_color(&self) -> Color {
+        Color::from(match self {
+            PickupEffect::Heal(_) => WHITE,
+            PickupEffect::ExtraLife => MAGENTA,
+            PickupEffect::PowerUp { power_up, .. } => match power_up {
+                PowerUp::Shield => AQUA,
+                PowerUp::RapidFire => ORANGE,
+                PowerUp::Multishot(_) => LIME,
+                PowerUp::Piercing => SILVER,
+                PowerUp::SlowMotion(_) => MEDIUM_PURPLE,
+                PowerUp::Magnet(_) => CRIMSON,
+                PowerUp::ScoreMultiplier(_) => GOLD,
+            },
+        })
+    }
+}
+
+/// Something floating in the play area that the
+/// player can fly into to collect, before it times out.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct Pickup {
+    effect: PickupEffect,
+    timer: Timer,
+}
+
+/// Pulls pickups within `radius` in towards the entity.
+///
+/// The radius is scaled up by the [`PowerUp::Magnet`] power up.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct PickupMagnet {
+    pub radius: f32,
+}
+
+#[derive(Event, Debug)]
+pub struct SpawnPickup {
+    position: Vec3,
+    effect: PickupEffect,
+}
+
+impl SpawnPickup {
+    pub fn new(position: Vec3, effect: PickupEffect) -> Self {
+        Self { position, effect }
+    }
+}
+
+fn spawn_pickup(
+    trigger: Trigger<SpawnPickup>,
+    mut commands: Commands,
+    assets: Res<PickupAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    const PICKUP_SECS: f32 = 20.;
+    const PICKUP_RADIUS: f32 = 20.;
+
+    let event = trigger.event();
+
+    let mut pickup = commands.spawn((
+        Name::new(event.effect.get_name()),
+        Pickup {
+            effect: event.effect,
+            timer: Timer::from_seconds(PICKUP_SECS, TimerMode::Once),
+        },
+        StateScoped(GameState::Playing),
+        Collider::circle(PICKUP_RADIUS),
+        Sensor,
+    ));
+
+    // Health keeps its own sprite, and everything else
+    // is told apart by colour until it gets one too.
+    match event.effect {
+        PickupEffect::Heal(_) => pickup.insert(SpriteBundle {
+            transform: Transform {
+                translation: event.position,
+                scale: Vec3::new(0.5, 0.5, 1.0),
+                ..default()
+            },
+            texture: assets.health_pickup.clone(),
+            ..default()
+        }),
+        effect => pickup.insert(MaterialMesh2dBundle {
+            transform: Transform::from_translation(event.position),
+            mesh: Mesh2dHandle(meshes.add(Rhombus::new(PICKUP_RADIUS * 1.5, PICKUP_RADIUS * 2.))),
+            material: materials.add(effect.get_color()),
+            ..default()
+        }),
+    };
+}
+
+fn pickup_tick(mut query: Query<&mut Pickup>, time: Res<Time>) {
+    for mut pickup in &mut query {
+        pickup.timer.tick(time.delta());
+    }
+}
+
+/// Blink pickups that are about to time out,
+/// faster still in their last moments.
+fn pickup_blink(mut query: Query<(&Pickup, &mut Visibility)>) {
+    const WARNING_SECS: f32 = 5.;
+    const URGENT_SECS: f32 = 2.;
+    const BLINKS_PER_SEC: f32 = 3.;
+    const URGENT_BLINKS_PER_SEC: f32 = 8.;
+
+    for (pickup, mut visibility) in &mut query {
+        let remaining = pickup.timer.remaining_secs();
+
+        let blinks_per_sec = if remaining > WARNING_SECS {
+            *visibility = Visibility::Inherited;
+            continue;
+        } else if remaining > URGENT_SECS {
+            BLINKS_PER_SEC
+        } else {
+            URGENT_BLINKS_PER_SEC
+        };
+
+        *visibility = if (remaining * blinks_per_sec).fract() < 0.5 {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+}
+
+fn pickup_magnet(
+    magnet_query: Query<(&PickupMagnet, &Transform, Option<&PowerUps>), Without<Pickup>>,
+    mut pickup_query: Query<&mut Transform, With<Pickup>>,
+    time: Res<Time<Fixed>>,
+) {
+    // How fast pickups are pulled in at the edge of
+    // the radius, and right up against the magnet.
+    const MIN_PULL_SPEED: f32 = 100.;
+    const MAX_PULL_SPEED: f32 = 500.;
+
+    for mut pickup_transform in &mut pickup_query {
+        for (magnet, magnet_transform, power_ups) in &magnet_query {
+            let radius = magnet.radius * power_ups.map_or(1., PowerUps::magnet_scale);
+            let distance = pickup_transform
+                .translation
+                .xy()
+                .distance(magnet_transform.translation.xy());
+            if radius <= 0. || distance > radius {
+                continue;
+            }
+
+            let speed = MAX_PULL_SPEED.lerp(MIN_PULL_SPEED, distance / radius);
+            let target = magnet_transform.translation.xy().extend(pickup_transform.translation.z);
+            pickup_transform.translation = pickup_transform
+                .translation
+                .move_towards(target, speed * time.delta_seconds());
+            break;
+        }
+    }
+}
+
+fn pickup_timeout(
+    query: Query<(&Pickup, Entity)>,
+    mut destroyed_event_writer: EventWriter<Destroyed>,
+) {
+    for (pickup, entity) in &query {
+        if pickup.timer.just_finished() {
+            destroyed_event_writer.send(Destroyed(entity));
+        }
+    }
+}
+
+fn pickup_collision(
+    mut collision_event_reader: EventReader<CollisionStarted>,
+    mut destroyed_event_writer: EventWriter<Destroyed>,
+    mut player_query: Query<(&mut Health, &mut ExtraLives, &mut PowerUps), With<Player>>,
+    pickup_query: Query<&Pickup>,
+) {
+    // More than one ship can touch a pickup in the same
+    // frame, but only the first of them collects it.
+    let mut collected = HashSet::new();
+    for CollisionStarted(entity1, entity2) in collision_event_reader.read() {
+        let mut logic = |first_entity: &Entity, second_entity: &Entity| {
+            let Ok(pickup) = pickup_query.get(*first_entity) else {
+                return;
+            };
+
+            let Ok((mut health, mut extra_lives, mut power_ups)) =
+                player_query.get_mut(*second_entity)
+            else {
+                return;
+            };
+
+            if !collected.insert(*first_entity) {
+                return;
+            }
+
+            match pickup.effect {
+                PickupEffect::Heal(amount) => health.add(amount),
+                PickupEffect::ExtraLife => extra_lives.0 = extra_lives.0.saturating_add(1),
+                PickupEffect::PowerUp { power_up, secs } => power_ups.add(power_up, secs),
+            }
+
+            destroyed_event_writer.send(Destroyed(*first_entity));
+        };
+
+        logic(entity1, entity2);
+        logic(entity2, entity1);
+    }
+}
+
+fn pickup_destroyed(
+    mut destroyed_event_reader: EventReader<Destroyed>,
+    pickup_query: Query<(), With<Pickup>>,
+    mut commands: Commands,
+) {
+    for Destroyed(entity) in destroyed_event_reader.read() {
+        if pickup_query.contains(*entity) {
+            commands.entity(*entity).despawn_recursive();
+        }
+    }
+}
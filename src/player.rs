@@ -1,11 +1,15 @@
 use avian2d::prelude::*;
-use bevy::{color::palettes::css::GREEN, prelude::*};
+use bevy::{
+    color::palettes::css::{GREEN, LIGHT_CORAL, LIGHT_GREEN, LIGHT_SKY_BLUE, WHITE},
+    ecs::system::SystemParam,
+    prelude::*,
+};
 use bevy_asset_loader::prelude::*;
 use bevy_transform_interpolation::{RotationInterpolation, TranslationInterpolation};
 use leafwing_input_manager::prelude::*;
 
 use crate::{
-    asteroid::Asteroid, destruction::Destroyed, projectile::SpawnProjectile, states::GameState, stats::{AngularAcceleration, Health, LinearAcceleration}, viewport_bound::WrapMovement, Action
+    asteroid::Asteroid, autopilot::{Autopilot, AutopilotEnabled}, destruction::Destroyed, faction::Faction, magnetism::Attracted, game_mode::GameMode, network::{LocalInput, NetworkSession, RemotePlayer}, pickup::PickupMagnet, power_up::{PowerUp, PowerUps}, projectile::{Shootable, Shot, SpawnProjectile}, round::RoundMode, run_config::RunConfig, states::GameState, stats::{AngularAcceleration, Health, LinearAcceleration}, viewport_bound::WrapMovement, Action
 };
 
 pub fn plugin(app: &mut App) {
@@ -13,6 +17,7 @@ pub fn plugin(app: &mut App) {
         LoadingStateConfig::new(GameState::Loading).load_collection::<PlayerAssets>(),
     );
     app.observe(spawn_player);
+    app.add_event::<PlayerShotDown>();
     app.add_systems(FixedUpdate, move_player);
     app.add_systems(
         Update,
@@ -20,7 +25,7 @@ pub fn plugin(app: &mut App) {
             engine_exhaust_visibility,
             visualize_player_health,
             player_shoot,
-            (player_destruction, collision_with_asteroid).chain(),
+            (player_destruction, collision_with_asteroid, shot_player).chain(),
         ),
     );
 }
@@ -38,45 +43,194 @@ struct PlayerAssets {
 #[reflect(Component)]
 pub struct Player;
 
+/// Which player a ship belongs to, counting from 0,
+/// when there is more than one of them.
+#[derive(Component, Reflect, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[reflect(Component)]
+pub struct PlayerNumber(pub u8);
+
+impl PlayerNumber {
+    pub fn get_color(&self) -> Color {
+        const COLORS: [Srgba; 4] = [WHITE, LIGHT_SKY_BLUE, LIGHT_CORAL, LIGHT_GREEN];
+        Color::from(COLORS[self.0 as usize % COLORS.len()])
+    }
+
+    /// Where the ship first appears, spread
+    /// out across the middle of the screen.
+    fn get_spawn_position(&self, player_count: u8) -> Vec3 {
+        const SPACING: f32 = 200.;
+
+        let offset = self.0 as f32 - (player_count.max(1) - 1) as f32 / 2.;
+        Vec3::new(offset * SPACING, 0.0, 0.0)
+    }
+
+    /// The keyboard, and gamepad if there is one, controlling the ship.
+    ///
+    /// A lone player gets every key, otherwise the keyboard is
+    /// split in two, with the first player on the left of it.
+    fn get_input_map(&self, player_count: u8, gamepad: Option<Gamepad>) -> InputMap<Action> {
+        let mut input_map = InputMap::default();
+
+        if player_count <= 1 || self.0 == 0 {
+            input_map = input_map
+                .with_axis(Action::Rotate, KeyboardVirtualAxis::AD)
+                .with(Action::Shoot, KeyCode::Space)
+                .with(Action::Move, KeyCode::KeyW);
+        }
+
+        if player_count <= 1 || self.0 == 1 {
+            input_map = input_map
+                .with_axis(Action::Rotate, KeyboardVirtualAxis::HORIZONTAL_ARROW_KEYS)
+                .with(Action::Move, KeyCode::ArrowUp);
+        }
+
+        if player_count > 1 && self.0 == 1 {
+            input_map = input_map
+                .with(Action::Shoot, KeyCode::Enter)
+                .with(Action::Shoot, KeyCode::NumpadEnter);
+        }
+
+        if let Some(gamepad) = gamepad {
+            input_map = input_map
+                .with_axis(Action::Rotate, GamepadControlAxis::LEFT_X)
+                .with(Action::Move, GamepadButtonType::RightTrigger2)
+                .with(Action::Shoot, GamepadButtonType::South)
+                .with_gamepad(gamepad);
+        }
+
+        input_map
+    }
+}
+
+/// How many more times the player can be destroyed,
+/// and come back, before the game is over.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct ExtraLives(pub u8);
+
+/// How long to wait between shots while shoot is held,
+/// which only keeps firing with rapid fire.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ShootCooldown(Timer);
+
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 struct EngineExhaust;
 
+/// Sent when a player is destroyed by a projectile
+/// fired by someone, most likely another player.
 #[derive(Event, Debug)]
-pub struct SpawnPlayer;
+pub struct PlayerShotDown {
+    pub player: Entity,
+    pub shooter: Entity,
+}
 
-fn spawn_player(_: Trigger<SpawnPlayer>, mut commands: Commands, assets: Res<PlayerAssets>) {
+#[derive(Event, Debug)]
+pub struct SpawnPlayer {
+    number: PlayerNumber,
+}
+
+impl SpawnPlayer {
+    pub fn new(number: u8) -> Self {
+        Self {
+            number: PlayerNumber(number),
+        }
+    }
+}
+
+/// What decides who, or what, controls a ship.
+#[derive(SystemParam)]
+struct ShipControl<'w> {
+    gamepads: Res<'w, Gamepads>,
+    network_session: Option<Res<'w, NetworkSession>>,
+    autopilot_enabled: Res<'w, AutopilotEnabled>,
+}
+
+fn spawn_player(
+    trigger: Trigger<SpawnPlayer>,
+    mut commands: Commands,
+    assets: Res<PlayerAssets>,
+    game_mode: Res<GameMode>,
+    run_config: Res<RunConfig>,
+    control: ShipControl,
+) {
     const ENGINE_EXHAUST_OFFSET: f32 = 48.;
+    const RAPID_FIRE_SECS: f32 = 0.1;
+    const PICKUP_MAGNET_RADIUS: f32 = 80.;
+
+    let number = trigger.event().number;
+    let player_count = game_mode.player_count();
+    let color = number.get_color();
+
+    let mut player = commands.spawn((
+        Name::new(format!("Player{}", number.0 + 1)),
+        StateScoped(GameState::Playing),
+        Player,
+        number,
+    ));
 
-    let input_map = InputMap::default()
-        .with_axis(Action::Rotate, KeyboardVirtualAxis::AD)
-        .with_axis(Action::Rotate, KeyboardVirtualAxis::HORIZONTAL_ARROW_KEYS)
-        .with(Action::Shoot, KeyCode::Space)
-        .with(Action::Move, KeyCode::KeyW)
-        .with(Action::Move, KeyCode::ArrowUp);
-
-    commands
-        .spawn((
-            Name::new("Player"),
-            StateScoped(GameState::Playing),
-            Player,
-            Health::new(3),
+    // Online, only this instance's own ship is controlled
+    // from here, with the whole keyboard and first gamepad.
+    let ShipControl { gamepads, network_session, autopilot_enabled } = control;
+    match &network_session {
+        Some(session) if !session.is_local(number) => {
+            player.insert((RemotePlayer, ActionState::<Action>::default()));
+        }
+        _ if autopilot_enabled.0 => {
+            player.insert((Autopilot, ActionState::<Action>::default()));
+        }
+        // The ship plays back its inputs a few ticks late, the same
+        // as on every other instance, so the keyboard drives a
+        // separate entity that they're read from.
+        Some(_) => {
+            player.insert(ActionState::<Action>::default());
+            player.with_children(|children| {
+                children.spawn((
+                    Name::new("LocalInput"),
+                    LocalInput,
+                    InputManagerBundle::with_map(number.get_input_map(1, gamepads.iter().next())),
+                ));
+            });
+        }
+        None => {
+            player.insert(InputManagerBundle::with_map(number.get_input_map(
+                player_count,
+                gamepads.iter().nth(number.0 as usize),
+            )));
+        }
+    }
+
+    player
+        .insert((
+            Health::new(run_config.player_health()),
+            (
+                ExtraLives(0),
+                PowerUps::default(),
+                ShootCooldown(Timer::from_seconds(RAPID_FIRE_SECS, TimerMode::Once)),
+                PickupMagnet {
+                    radius: PICKUP_MAGNET_RADIUS,
+                },
+            ),
+            Shootable,
+            Faction::Player,
             RigidBody::Kinematic,
             Collider::triangle(
                 Vec2::new(-30.0, -28.0),
                 Vec2::new(30.0, -28.0),
                 Vec2::new(0.0, 30.0),
             ),
-            TranslationInterpolation,
-            RotationInterpolation,
+            (TranslationInterpolation, RotationInterpolation),
             WrapMovement,
+            Attracted(1.),
             LinearAcceleration(250.),
             AngularAcceleration(2.5),
             SpriteBundle {
+                transform: Transform::from_translation(number.get_spawn_position(player_count)),
+                sprite: Sprite { color, ..default() },
                 texture: assets.sprite.clone(),
                 ..default()
             },
-            InputManagerBundle::with_map(input_map),
         ))
         .with_children(|children| {
             children.spawn((
@@ -84,6 +238,7 @@ fn spawn_player(_: Trigger<SpawnPlayer>, mut commands: Commands, assets: Res<Pla
                 EngineExhaust,
                 SpriteBundle {
                     transform: Transform::from_xyz(0.0, -ENGINE_EXHAUST_OFFSET, -1.0),
+                    sprite: Sprite { color, ..default() },
                     texture: assets.engine_exhaust.clone(),
                     ..default()
                 },
@@ -108,6 +263,7 @@ fn move_player(
         With<Player>,
     >,
     time: Res<Time<Fixed>>,
+    run_config: Res<RunConfig>,
 ) {
     const MAX_LINEAR_SPEED: f32 = 400.0;
     const MAX_ANGULAR_SPEED: f32 = 3.;
@@ -122,7 +278,7 @@ fn move_player(
         transform,
     ) in &mut query
     {
-        let rotation = action_state.clamped_value(&Action::Rotate);
+        let rotation = action_state.clamped_value(&Action::Rotate) * run_config.rotation_scale();
         let direction = (transform.rotation * Vec3::Y).xy().normalize_or_zero();
 
         if action_state.pressed(&Action::Move) {
@@ -158,48 +314,156 @@ fn move_player(
 }
 
 fn player_shoot(
-    query: Query<(&ActionState<Action>, &Transform), With<Player>>,
+    mut query: Query<
+        (
+            Entity,
+            &ActionState<Action>,
+            &Transform,
+            &PowerUps,
+            &mut ShootCooldown,
+        ),
+        With<Player>,
+    >,
+    time: Res<Time>,
     mut commands: Commands,
 ) {
-    for (action_state, transform) in &query {
-        if action_state.just_pressed(&Action::Shoot) {
-            const PROJECTILE_SPAWN_OFFSET: f32 = 30.;
+    const PROJECTILE_SPAWN_OFFSET: f32 = 30.;
+    // The angle between each projectile of a multishot.
+    const SPREAD_ANGLE: f32 = 0.2;
+
+    for (entity, action_state, transform, power_ups, mut cooldown) in &mut query {
+        cooldown.0.tick(time.delta());
+
+        let shoot = action_state.just_pressed(&Action::Shoot)
+            || (power_ups.rapid_fire()
+                && action_state.pressed(&Action::Shoot)
+                && cooldown.0.finished());
+        if !shoot {
+            continue;
+        }
+
+        cooldown.0.reset();
+
+        let shot_count = power_ups.shot_count();
+        for n in 0..shot_count {
+            let angle = (n as f32 - (shot_count - 1) as f32 / 2.) * SPREAD_ANGLE;
+            let rotation = transform.rotation * Quat::from_rotation_z(angle);
 
-            let direction = (transform.rotation * Vec3::Y).normalize_or_zero();
+            let direction = (rotation * Vec3::Y).normalize_or_zero();
             let offset = (direction.xy() * PROJECTILE_SPAWN_OFFSET).extend(0.0);
 
-            commands.trigger(SpawnProjectile::new(
-                transform.translation + offset,
-                transform.rotation,
-            ))
+            commands.trigger(
+                SpawnProjectile::new(transform.translation + offset, rotation)
+                    .with_piercing(power_ups.piercing())
+                    .with_shooter(entity),
+            );
         }
     }
 }
 
+/// Bring the player back where they first appeared, briefly
+/// shielded, if they have an extra life to spend, or the mode
+/// has players come back forever; otherwise they are out,
+/// and once every player is out the game is over.
+///
+/// In the sandbox the player just carries on where they are.
+#[allow(clippy::type_complexity)]
 fn player_destruction(
     mut destroyed_event_reader: EventReader<Destroyed>,
-    query: Query<(), With<Player>>,
+    mut query: Query<
+        (
+            Entity,
+            &PlayerNumber,
+            &mut ExtraLives,
+            &mut Health,
+            &mut PowerUps,
+            &mut Transform,
+            &mut LinearVelocity,
+            &mut AngularVelocity,
+        ),
+        With<Player>,
+    >,
+    game_mode: Res<GameMode>,
+    round_mode: Res<RoundMode>,
     mut commands: Commands,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
+    const SPAWN_PROTECTION_SECS: f32 = 2.;
+
+    let mut out = vec![];
     for Destroyed(entity) in destroyed_event_reader.read() {
-        if query.contains(*entity) {
+        let Ok((
+            _,
+            number,
+            mut extra_lives,
+            mut health,
+            mut power_ups,
+            mut transform,
+            mut linear_velocity,
+            mut angular_velocity,
+        )) = query.get_mut(*entity)
+        else {
+            continue;
+        };
+
+        // Being destroyed more than once in the same
+        // frame must only cost a single life.
+        if health.current() != 0 {
+            continue;
+        }
+
+        if *round_mode == RoundMode::Sandbox && !game_mode.is_versus() {
+            health.restore();
+            continue;
+        }
+
+        if game_mode.is_versus() || extra_lives.0 > 0 {
+            if !game_mode.is_versus() {
+                extra_lives.0 -= 1;
+            }
+            health.restore();
+            power_ups.clear();
+            power_ups.add(PowerUp::Shield, SPAWN_PROTECTION_SECS);
+            *transform = Transform::from_translation(
+                number.get_spawn_position(game_mode.player_count()),
+            );
+            linear_velocity.0 = Vec2::ZERO;
+            angular_velocity.0 = 0.0;
+        } else {
             commands.entity(*entity).despawn_recursive();
-            next_state.set(GameState::GameOver);
+            out.push(*entity);
         }
     }
+
+    if out.is_empty() {
+        return;
+    }
+
+    // A ship at zero health that hasn't been
+    // handled yet may still have a life to spend.
+    let any_left = query.iter().any(|(entity, _, extra_lives, health, ..)| {
+        !out.contains(&entity) && (health.current() > 0 || extra_lives.0 > 0)
+    });
+    if !any_left {
+        next_state.set(GameState::GameOver);
+    }
 }
 
 fn collision_with_asteroid(
     mut collision_event_reader: EventReader<CollisionStarted>,
     mut destroyed_event_writer: EventWriter<Destroyed>,
-    mut player_query: Query<&mut Health, With<Player>>,
+    mut player_query: Query<(&mut Health, &PowerUps), With<Player>>,
     asteroid_query: Query<(), With<Asteroid>>,
+    run_config: Res<RunConfig>,
 ) {
     for CollisionStarted(entity1, entity2) in collision_event_reader.read() {
         let mut logic = |first_entity: &Entity, second_entity: &Entity| {
             if player_query.contains(*first_entity) && asteroid_query.contains(*second_entity) {
-                let mut health = player_query.get_mut(*first_entity).unwrap();
+                let (mut health, power_ups) = player_query.get_mut(*first_entity).unwrap();
+                if power_ups.shielded() || run_config.asteroids_harmless() {
+                    return;
+                }
+
                 health.sub(1);
                 if health.current() == 0 {
                     destroyed_event_writer.send(Destroyed(*first_entity));
@@ -212,6 +476,35 @@ fn collision_with_asteroid(
     }
 }
 
+fn shot_player(
+    mut shot_event_reader: EventReader<Shot>,
+    mut destroyed_event_writer: EventWriter<Destroyed>,
+    mut player_shot_down_event_writer: EventWriter<PlayerShotDown>,
+    mut player_query: Query<(&mut Health, &PowerUps), With<Player>>,
+) {
+    for shot in shot_event_reader.read() {
+        let Ok((mut health, power_ups)) = player_query.get_mut(shot.entity) else {
+            continue;
+        };
+
+        if power_ups.shielded() {
+            continue;
+        }
+
+        health.sub(1);
+        if health.current() == 0 {
+            destroyed_event_writer.send(Destroyed(shot.entity));
+
+            if let Some(shooter) = shot.shooter {
+                player_shot_down_event_writer.send(PlayerShotDown {
+                    player: shot.entity,
+                    shooter,
+                });
+            }
+        }
+    }
+}
+
 /// Use gizmos to render a rectangle that
 /// represent the player's health.
 ///
@@ -224,18 +517,22 @@ fn collision_with_asteroid(
 /// code, but this is the simplest way to get a health
 /// widget rendering for the MVP.
 ///
+/// Any extra lives are shown as small ships in
+/// a column of their own, beside the health.
+///
 /// TODO: look into switching to a better
 /// health widget rendering method?
 fn visualize_player_health(
     mut gizmos: Gizmos,
-    player_query: Query<(&Health, &Transform), With<Player>>,
+    player_query: Query<(&Health, &ExtraLives, &Transform), With<Player>>,
 ) {
     const POSITION_OFFSET: f32 = 80.;
     const GAP_OFFSET: f32 = 4.;
     const HEALTH_SEGMENT_HEIGHT: f32 = 24.;
     const HEALTH_SEGMENT_WIDTH: f32 = 16.;
+    const EXTRA_LIFE_SIZE: f32 = 12.;
 
-    for (health, transform) in &player_query {
+    for (health, extra_lives, transform) in &player_query {
         for n in 0..health.current() {
             let gizmo_position = Vec2::new(
                 transform.translation.x - POSITION_OFFSET,
@@ -250,6 +547,25 @@ fn visualize_player_health(
                 GREEN,
             );
         }
+
+        for n in 0..extra_lives.0 {
+            let gizmo_position = Vec2::new(
+                transform.translation.x - POSITION_OFFSET - HEALTH_SEGMENT_WIDTH - GAP_OFFSET,
+                transform.translation.y + (EXTRA_LIFE_SIZE + GAP_OFFSET) * n as f32,
+            );
+
+            // The same upwards pointing triangle as the ship.
+            let half_size = EXTRA_LIFE_SIZE / 2.;
+            gizmos.linestrip_2d(
+                [
+                    gizmo_position + Vec2::new(-half_size, -half_size),
+                    gizmo_position + Vec2::new(half_size, -half_size),
+                    gizmo_position + Vec2::new(0., half_size),
+                    gizmo_position + Vec2::new(-half_size, -half_size),
+                ],
+                GREEN,
+            );
+        }
     }
 }
 
@@ -0,0 +1,452 @@
+use bevy::{color::palettes::css::AQUA, prelude::*};
+use serde::Deserialize;
+use sickle_ui::prelude::*;
+
+use crate::{player::Player, states::GameState, stats::ScoreMultiplier};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<PowerUps>();
+    app.register_type::<RecentlyExpired>();
+    app.init_resource::<RecentlyExpired>();
+    app.add_event::<PowerUpExpired>();
+    app.add_systems(OnEnter(GameState::Playing), (reset_power_up_effects, spawn_power_up_hud));
+    app.add_systems(OnExit(GameState::Playing), (reset_power_up_effects, clear_recently_expired));
+    app.add_systems(
+        Update,
+        (
+            power_ups_tick,
+            (
+                update_score_multiplier,
+                update_slow_motion.run_if(in_state(GameState::Playing)),
+                update_power_up_hud,
+            ),
+            visualize_shield,
+        )
+            .chain(),
+    );
+}
+
+/// A temporary boost to the player.
+#[derive(Reflect, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum PowerUp {
+    /// Stops the player taking any damage.
+    Shield,
+    /// Keeps firing for as long as shoot is held.
+    RapidFire,
+    /// Fires this many projectiles in a spread at once.
+    Multishot(u8),
+    /// Projectiles carry on through whatever they hit.
+    Piercing,
+    /// Slows the whole game down, by this much.
+    SlowMotion(f32),
+    /// Multiplies any points scored, once for each stack.
+    ScoreMultiplier(u32),
+    /// Multiplies the radius pickups are pulled in from.
+    Magnet(f32),
+}
+
+/// What happens when a power up is picked
+/// up while one of its kind is already active.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stacking {
+    /// Start its timer over, keeping the stronger of the two.
+    Refresh,
+    /// Add the new duration onto what is left, up to `max_secs`.
+    Extend { max_secs: f32 },
+    /// Count it as another stack, up to `max`,
+    /// and start its timer over.
+    Stack { max: u8 },
+}
+
+impl PowerUp {
+    /// Whether two power ups are the same kind,
+    /// regardless of how strong they are.
+    fn same_kind(&self, other: &Self) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+
+    pub fn get_stacking(&self) -> Stacking {
+        match self {
+            PowerUp::Shield => Stacking::Extend { max_secs: 20. },
+            PowerUp::RapidFire
+            | PowerUp::Multishot(_)
+            | PowerUp::Piercing
+            | PowerUp::Magnet(_) => Stacking::Refresh,
+            PowerUp::SlowMotion(_) => Stacking::Extend { max_secs: 10. },
+            PowerUp::ScoreMultiplier(_) => Stacking::Stack { max: 3 },
+        }
+    }
+
+    pub fn get_name(&self) -> String {
+        match self {
+            PowerUp::Shield => String::from("Shield"),
+            PowerUp::RapidFire => String::from("Rapid Fire"),
+            PowerUp::Multishot(count) => format!("Multishot x{count}"),
+            PowerUp::Piercing => String::from("Piercing"),
+            PowerUp::SlowMotion(_) => String::from("Slow Motion"),
+            PowerUp::ScoreMultiplier(multiplier) => format!("Score x{multiplier}"),
+            PowerUp::Magnet(_) => String::from("Magnet"),
+        }
+    }
+
+    /// Which of two power ups of the same kind is stronger.
+    fn strongest(self, other: Self) -> Self {
+        match (self, other) {
+            (PowerUp::Multishot(a), PowerUp::Multishot(b)) => PowerUp::Multishot(a.max(b)),
+            (PowerUp::SlowMotion(a), PowerUp::SlowMotion(b)) => PowerUp::SlowMotion(a.min(b)),
+            (PowerUp::ScoreMultiplier(a), PowerUp::ScoreMultiplier(b)) => {
+                PowerUp::ScoreMultiplier(a.max(b))
+            }
+            (PowerUp::Magnet(a), PowerUp::Magnet(b)) => PowerUp::Magnet(a.max(b)),
+            _ => other,
+        }
+    }
+}
+
+/// Sent when one of the player's power ups runs out.
+#[derive(Event, Debug)]
+pub struct PowerUpExpired {
+    pub entity: Entity,
+    pub power_up: PowerUp,
+}
+
+#[derive(Reflect, Debug)]
+pub struct ActivePowerUp {
+    power_up: PowerUp,
+    stacks: u8,
+    timer: Timer,
+}
+
+impl ActivePowerUp {
+    pub fn power_up(&self) -> PowerUp {
+        self.power_up
+    }
+
+    pub fn stacks(&self) -> u8 {
+        self.stacks
+    }
+
+    pub fn remaining_secs(&self) -> f32 {
+        self.timer.remaining_secs()
+    }
+}
+
+/// The power ups that are currently active on the player.
+#[derive(Component, Reflect, Debug, Default)]
+#[reflect(Component)]
+pub struct PowerUps(Vec<ActivePowerUp>);
+
+impl PowerUps {
+    /// Activate a power up for `secs`, following its
+    /// [`Stacking`] rule if one of its kind is already active.
+    pub fn add(&mut self, power_up: PowerUp, secs: f32) {
+        let Some(active) = self
+            .0
+            .iter_mut()
+            .find(|active| active.power_up.same_kind(&power_up))
+        else {
+            self.0.push(ActivePowerUp {
+                power_up,
+                stacks: 1,
+                timer: Timer::from_seconds(secs, TimerMode::Once),
+            });
+            return;
+        };
+
+        active.power_up = active.power_up.strongest(power_up);
+        match power_up.get_stacking() {
+            Stacking::Refresh => {
+                let secs = secs.max(active.timer.remaining_secs());
+                active.timer = Timer::from_seconds(secs, TimerMode::Once);
+            }
+            Stacking::Extend { max_secs } => {
+                let secs = (active.timer.remaining_secs() + secs).min(max_secs);
+                active.timer = Timer::from_seconds(secs, TimerMode::Once);
+            }
+            Stacking::Stack { max } => {
+                active.stacks = (active.stacks + 1).min(max);
+                active.timer = Timer::from_seconds(secs, TimerMode::Once);
+            }
+        }
+    }
+
+    /// End every power up straight away,
+    /// without sending [`PowerUpExpired`].
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ActivePowerUp> {
+        self.0.iter()
+    }
+
+    fn find(&self, predicate: impl Fn(&PowerUp) -> bool) -> Option<&ActivePowerUp> {
+        self.0.iter().find(|active| predicate(&active.power_up))
+    }
+
+    pub fn shielded(&self) -> bool {
+        self.find(|power_up| *power_up == PowerUp::Shield).is_some()
+    }
+
+    pub fn rapid_fire(&self) -> bool {
+        self.find(|power_up| *power_up == PowerUp::RapidFire).is_some()
+    }
+
+    pub fn piercing(&self) -> bool {
+        self.find(|power_up| *power_up == PowerUp::Piercing).is_some()
+    }
+
+    /// How many projectiles each shot fires.
+    pub fn shot_count(&self) -> u8 {
+        match self.find(|power_up| matches!(power_up, PowerUp::Multishot(_))) {
+            Some(ActivePowerUp {
+                power_up: PowerUp::Multishot(count),
+                ..
+            }) => (*count).max(1),
+            _ => 1,
+        }
+    }
+
+    /// How fast the game runs, relative to normal speed.
+    pub fn time_scale(&self) -> f32 {
+        match self.find(|power_up| matches!(power_up, PowerUp::SlowMotion(_))) {
+            Some(ActivePowerUp {
+                power_up: PowerUp::SlowMotion(scale),
+                ..
+            }) => *scale,
+            _ => 1.,
+        }
+    }
+
+    /// How much bigger the player's pickup magnet is.
+    pub fn magnet_scale(&self) -> f32 {
+        match self.find(|power_up| matches!(power_up, PowerUp::Magnet(_))) {
+            Some(ActivePowerUp {
+                power_up: PowerUp::Magnet(scale),
+                ..
+            }) => *scale,
+            _ => 1.,
+        }
+    }
+
+    pub fn score_multiplier(&self) -> u32 {
+        match self.find(|power_up| matches!(power_up, PowerUp::ScoreMultiplier(_))) {
+            Some(ActivePowerUp {
+                power_up: PowerUp::ScoreMultiplier(multiplier),
+                stacks,
+                ..
+            }) => multiplier.pow(*stacks as u32),
+            _ => 1,
+        }
+    }
+}
+
+/// The list of active power ups in the corner of the screen.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PowerUpHud;
+
+/// Power ups that just ran out, listed in the [`PowerUpHud`]
+/// until their timers finish, or the round ends.
+#[derive(Resource, Reflect, Default, Deref, DerefMut)]
+#[reflect(Resource)]
+struct RecentlyExpired(Vec<(PowerUp, Timer)>);
+
+/// Power ups run on real time, so that slow
+/// motion doesn't make itself last longer.
+fn power_ups_tick(
+    mut query: Query<(Entity, &mut PowerUps)>,
+    time: Res<Time<Real>>,
+    mut expired_event_writer: EventWriter<PowerUpExpired>,
+) {
+    for (entity, mut power_ups) in &mut query {
+        for active in &mut power_ups.0 {
+            active.timer.tick(time.delta());
+            if active.timer.finished() {
+                expired_event_writer.send(PowerUpExpired {
+                    entity,
+                    power_up: active.power_up,
+                });
+            }
+        }
+        power_ups.0.retain(|active| !active.timer.finished());
+    }
+}
+
+/// Put everything the power ups affect
+/// outside of the player back to normal.
+fn reset_power_up_effects(
+    mut score_multiplier: ResMut<ScoreMultiplier>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    score_multiplier.0 = 1;
+    time.set_relative_speed(1.);
+}
+
+fn clear_recently_expired(mut recently_expired: ResMut<RecentlyExpired>) {
+    recently_expired.clear();
+}
+
+fn update_score_multiplier(
+    query: Query<&PowerUps, With<Player>>,
+    mut score_multiplier: ResMut<ScoreMultiplier>,
+) {
+    score_multiplier.0 = query
+        .iter()
+        .map(PowerUps::score_multiplier)
+        .max()
+        .unwrap_or(1);
+}
+
+fn update_slow_motion(
+    query: Query<&PowerUps, With<Player>>,
+    mut time: ResMut<Time<Virtual>>,
+) {
+    let time_scale = query
+        .iter()
+        .map(PowerUps::time_scale)
+        .fold(1., f32::min);
+    if time.relative_speed() != time_scale {
+        time.set_relative_speed(time_scale);
+    }
+}
+
+fn spawn_power_up_hud(mut commands: Commands) {
+    commands
+        .ui_builder(UiRoot)
+        .column(|column| {
+            column.spawn((TextBundle::default(), PowerUpHud));
+        })
+        .style()
+        .position_type(PositionType::Absolute)
+        .bottom(Val::Px(16.))
+        .left(Val::Px(16.))
+        .entity_commands()
+        .insert(StateScoped(GameState::Playing));
+}
+
+/// Lists every active power up, and for a
+/// moment after, any that have just run out.
+fn update_power_up_hud(
+    mut hud_query: Query<&mut Text, With<PowerUpHud>>,
+    player_query: Query<&PowerUps, With<Player>>,
+    mut expired_event_reader: EventReader<PowerUpExpired>,
+    mut recently_expired: ResMut<RecentlyExpired>,
+    time: Res<Time<Real>>,
+) {
+    const EXPIRED_NOTICE_SECS: f32 = 1.5;
+
+    for (_, timer) in recently_expired.iter_mut() {
+        timer.tick(time.delta());
+    }
+    recently_expired.retain(|(_, timer)| !timer.finished());
+    recently_expired.extend(expired_event_reader.read().map(|expired| {
+        (
+            expired.power_up,
+            Timer::from_seconds(EXPIRED_NOTICE_SECS, TimerMode::Once),
+        )
+    }));
+
+    let lines: Vec<String> = player_query
+        .iter()
+        .flat_map(PowerUps::iter)
+        .map(|active| {
+            let stacks = if active.stacks() > 1 {
+                format!(" ({})", active.stacks())
+            } else {
+                String::new()
+            };
+            format!(
+                "{}{} {:.0}s",
+                active.power_up().get_name(),
+                stacks,
+                active.remaining_secs().ceil()
+            )
+        })
+        .chain(
+            recently_expired
+                .iter()
+                .map(|(power_up, _)| format!("{} ended", power_up.get_name())),
+        )
+        .collect();
+
+    for mut text in &mut hud_query {
+        *text = Text::from_section(lines.join("\n"), TextStyle::default());
+    }
+}
+
+fn visualize_shield(mut gizmos: Gizmos, query: Query<(&PowerUps, &Transform)>) {
+    const SHIELD_RADIUS: f32 = 48.;
+
+    for (power_ups, transform) in &query {
+        if power_ups.shielded() {
+            gizmos.circle_2d(transform.translation.xy(), SHIELD_RADIUS, AQUA);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn only(power_ups: &PowerUps) -> &ActivePowerUp {
+        assert_eq!(power_ups.0.len(), 1);
+        &power_ups.0[0]
+    }
+
+    #[test]
+    fn different_kinds_are_active_side_by_side() {
+        let mut power_ups = PowerUps::default();
+        power_ups.add(PowerUp::Shield, 5.);
+        power_ups.add(PowerUp::RapidFire, 5.);
+
+        assert!(power_ups.shielded());
+        assert!(power_ups.rapid_fire());
+    }
+
+    #[test]
+    fn refresh_keeps_the_stronger_and_longer() {
+        let mut power_ups = PowerUps::default();
+        power_ups.add(PowerUp::Multishot(5), 10.);
+        power_ups.add(PowerUp::Multishot(3), 4.);
+
+        let active = only(&power_ups);
+        assert_eq!(active.power_up(), PowerUp::Multishot(5));
+        assert_eq!(active.stacks(), 1);
+        assert_eq!(active.remaining_secs(), 10.);
+    }
+
+    #[test]
+    fn refresh_starts_the_timer_over() {
+        let mut power_ups = PowerUps::default();
+        power_ups.add(PowerUp::Piercing, 8.);
+        power_ups.0[0].timer.tick(Duration::from_secs(6));
+        power_ups.add(PowerUp::Piercing, 8.);
+
+        assert_eq!(only(&power_ups).remaining_secs(), 8.);
+    }
+
+    #[test]
+    fn extend_adds_on_up_to_the_limit() {
+        let mut power_ups = PowerUps::default();
+        power_ups.add(PowerUp::Shield, 8.);
+        power_ups.add(PowerUp::Shield, 8.);
+        assert_eq!(only(&power_ups).remaining_secs(), 16.);
+
+        power_ups.add(PowerUp::Shield, 8.);
+        assert_eq!(only(&power_ups).remaining_secs(), 20.);
+    }
+
+    #[test]
+    fn stack_counts_up_to_the_limit() {
+        let mut power_ups = PowerUps::default();
+        for _ in 0..5 {
+            power_ups.add(PowerUp::ScoreMultiplier(2), 15.);
+        }
+
+        assert_eq!(only(&power_ups).stacks(), 3);
+        assert_eq!(power_ups.score_multiplier(), 8);
+    }
+}
@@ -4,7 +4,7 @@ use bevy_asset_loader::prelude::*;
 use bevy_transform_interpolation::*;
 
 use crate::{
-    destruction::Destroyed, states::GameState, stats::LinearAcceleration, viewport_bound::DestroyOutOfBounds
+    destruction::Destroyed, faction::{Faction, FactionMatrix}, magnetism::Attracted, states::GameState, stats::LinearAcceleration, viewport_bound::DestroyOutOfBounds
 };
 
 pub fn plugin(app: &mut App) {
@@ -23,22 +23,59 @@ struct ProjectileAssets {
     projectile_sprite: Handle<Image>,
 }
 
+/// Sent when a [`Shootable`] entity is hit by a projectile.
 #[derive(Event, Debug)]
-pub struct Shot(pub Entity);
+pub struct Shot {
+    pub entity: Entity,
+    /// The direction the projectile was travelling.
+    pub direction: Vec2,
+    /// The outward normal of the side of the entity
+    /// that was hit, in the entity's local space.
+    pub local_normal: Vec2,
+    /// Whoever fired the projectile, if anyone did.
+    pub shooter: Option<Entity>,
+}
 
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 pub struct Shootable;
 
+/// The speed projectiles accelerate up to.
+pub const PROJECTILE_SPEED: f32 = 1000.0;
+
 #[derive(Event, Debug)]
 pub struct SpawnProjectile {
     position: Vec3,
     rotation: Quat,
+    faction: Faction,
+    piercing: bool,
+    shooter: Option<Entity>,
 }
 
 impl SpawnProjectile {
     pub fn new(position: Vec3, rotation: Quat) -> Self {
-        Self { position, rotation }
+        Self {
+            position,
+            rotation,
+            faction: Faction::Player,
+            piercing: false,
+            shooter: None,
+        }
+    }
+
+    pub fn with_faction(mut self, faction: Faction) -> Self {
+        self.faction = faction;
+        self
+    }
+
+    pub fn with_piercing(mut self, piercing: bool) -> Self {
+        self.piercing = piercing;
+        self
+    }
+
+    pub fn with_shooter(mut self, shooter: Entity) -> Self {
+        self.shooter = Some(shooter);
+        self
     }
 }
 
@@ -46,19 +83,32 @@ impl SpawnProjectile {
 #[reflect(Component)]
 struct Projectile;
 
+/// Whoever fired the projectile, which it can't hit.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Shooter(Entity);
+
+/// Keeps a projectile going after it hits something.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct Piercing;
+
 fn spawn_projectile(
     trigger: Trigger<SpawnProjectile>,
     mut commands: Commands,
     assets: Res<ProjectileAssets>,
 ) {
     let event = trigger.event();
-    commands.spawn((
+    let mut projectile = commands.spawn((
         Name::new("Projectile"),
         Projectile,
+        event.faction,
         StateScoped(GameState::Playing),
         DestroyOutOfBounds,
         RigidBody::Kinematic,
         Collider::circle(4.),
+        Sensor,
+        Attracted(4.),
         DebugRender::default(),
         LinearAcceleration(8000.),
         TranslationInterpolation,
@@ -72,35 +122,91 @@ fn spawn_projectile(
             ..default()
         },
     ));
+
+    if event.piercing {
+        projectile.insert(Piercing);
+    }
+
+    if let Some(shooter) = event.shooter {
+        projectile.insert(Shooter(shooter));
+    }
 }
 
 fn move_projectile(
     mut query: Query<(&mut LinearVelocity, &Transform, &LinearAcceleration), With<Projectile>>,
     time: Res<Time<Fixed>>,
 ) {
-    const MAX_PROJECTILE_SPEED: f32 = 1000.0;
-
     for (mut linear_velocity, transform, linear_acceleration) in &mut query {
-        let direction = (transform.rotation * Vec3::Y).xy().normalize_or_zero();
+        // Once the projectile is moving, keep it heading the way it
+        // is travelling rather than the way it was fired, so that
+        // anything bending its path, like magnetic fields, sticks.
+        let direction = linear_velocity
+            .0
+            .try_normalize()
+            .unwrap_or_else(|| (transform.rotation * Vec3::Y).xy().normalize_or_zero());
         linear_velocity.0 = linear_velocity.0.move_towards(
-            direction * MAX_PROJECTILE_SPEED,
+            direction * PROJECTILE_SPEED,
             linear_acceleration.0 * time.delta_seconds(),
         );
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn shoot_collisions(
     mut collision_event_reader: EventReader<CollisionStarted>,
     mut shot_event_writer: EventWriter<Shot>,
     mut destroyed_event_writer: EventWriter<Destroyed>,
-    shootable_query: Query<(), With<Shootable>>,
-    projectile_query: Query<(), With<Projectile>>,
+    collisions: Res<Collisions>,
+    faction_matrix: Res<FactionMatrix>,
+    shootable_query: Query<(&GlobalTransform, Option<&Faction>), With<Shootable>>,
+    projectile_query: Query<
+        (
+            &LinearVelocity,
+            &GlobalTransform,
+            &Faction,
+            Has<Piercing>,
+            Option<&Shooter>,
+        ),
+        With<Projectile>,
+    >,
 ) {
     for CollisionStarted(entity1, entity2) in collision_event_reader.read() {
         let mut logic = |first_entity: &Entity, second_entity: &Entity| {
-            if shootable_query.contains(*first_entity) && projectile_query.contains(*second_entity)
-            {
-                shot_event_writer.send(Shot(*first_entity));
+            let Ok((shootable_transform, shootable_faction)) = shootable_query.get(*first_entity)
+            else {
+                return;
+            };
+
+            let Ok((linear_velocity, projectile_transform, projectile_faction, piercing, shooter)) =
+                projectile_query.get(*second_entity)
+            else {
+                return;
+            };
+
+            let shooter = shooter.map(|Shooter(shooter)| *shooter);
+            if shooter == Some(*first_entity) {
+                return;
+            }
+
+            // Projectiles pass straight through
+            // anything they aren't allowed to damage.
+            let shootable_faction = shootable_faction.copied().unwrap_or_default();
+            if !faction_matrix.can_damage(*projectile_faction, shootable_faction) {
+                return;
+            }
+
+            shot_event_writer.send(Shot {
+                entity: *first_entity,
+                direction: linear_velocity.0.normalize_or_zero(),
+                local_normal: local_hit_normal(
+                    &collisions,
+                    (*first_entity, shootable_transform),
+                    (*second_entity, projectile_transform),
+                ),
+                shooter,
+            });
+
+            if !piercing {
                 destroyed_event_writer.send(Destroyed(*second_entity));
             }
         };
@@ -110,6 +216,40 @@ fn shoot_collisions(
     }
 }
 
+/// The outward normal of the side of `hit` that `other` hit,
+/// in the local space of `hit`.
+///
+/// Uses the global transforms, as `hit` may be a part
+/// of a bigger body, like one piece of a boss.
+///
+/// This comes from the contact manifold of their collision,
+/// falling back onto the direction from the centre of `hit`
+/// to `other` if there are no contacts to go by.
+fn local_hit_normal(
+    collisions: &Collisions,
+    (hit_entity, hit_transform): (Entity, &GlobalTransform),
+    (other_entity, other_transform): (Entity, &GlobalTransform),
+) -> Vec2 {
+    let manifold_normal = collisions
+        .get(hit_entity, other_entity)
+        .and_then(|contacts| {
+            let manifold = contacts.manifolds.first()?;
+            Some(if contacts.entity1 == hit_entity {
+                manifold.normal1
+            } else {
+                manifold.normal2
+            })
+        });
+
+    manifold_normal.unwrap_or_else(|| {
+        let hit_transform = hit_transform.compute_transform();
+        let offset = other_transform.translation() - hit_transform.translation;
+        (hit_transform.rotation.inverse() * offset)
+            .xy()
+            .normalize_or_zero()
+    })
+}
+
 fn destroy_projectiles(
     mut event_reader: EventReader<Destroyed>,
     query: Query<(), With<Projectile>>,
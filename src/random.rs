@@ -0,0 +1,120 @@
+use bevy::prelude::*;
+use rand::{rngs::StdRng, thread_rng, Rng, SeedableRng};
+
+use crate::states::GameState;
+
+pub fn plugin(app: &mut App) {
+    app.init_resource::<GameRng>();
+    app.add_systems(OnEnter(GameState::Playing), reseed_game_rng);
+}
+
+/// The seeded random number generator that
+/// gameplay randomness should be drawn from,
+/// so that a round can be replayed from its seed.
+///
+/// It derefs to the main stream, which only the layout and the
+/// spawners draw from. Anything that draws depending on what the
+/// player does, or on the frame rate, has a stream of its own, so
+/// that it doesn't change what spawns next: drops and saucers
+/// here, and each asteroid from a seed it's spawned with.
+#[derive(Resource, Deref, DerefMut)]
+pub struct GameRng {
+    seed: u64,
+    #[deref]
+    rng: StdRng,
+    drops: StdRng,
+    saucers: StdRng,
+}
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self::new(thread_rng().gen())
+    }
+}
+
+impl GameRng {
+    const DROPS_STREAM: u64 = 0xD809_5EED;
+    const SAUCERS_STREAM: u64 = 0x5A0C_E125;
+
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+            drops: seeded_stream(seed, Self::DROPS_STREAM),
+            saucers: seeded_stream(seed, Self::SAUCERS_STREAM),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The stream that drops are rolled from.
+    pub fn drops(&mut self) -> &mut StdRng {
+        &mut self.drops
+    }
+
+    /// The stream that saucers spawn, fly and shoot from.
+    pub fn saucers(&mut self) -> &mut StdRng {
+        &mut self.saucers
+    }
+}
+
+/// One of the streams of random numbers derived from `seed`,
+/// told apart by `stream`, so each can be drawn from as much
+/// as it needs without shifting what comes out of the others.
+pub fn seeded_stream(seed: u64, stream: u64) -> StdRng {
+    StdRng::seed_from_u64(seed ^ stream)
+}
+
+/// When present, every round starts from this seed
+/// rather than a fresh one, such as when several
+/// instances need to play out the same round.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct FixedSeed(pub u64);
+
+/// Start every round from a fresh seed,
+/// unless a [`FixedSeed`] has been set.
+pub fn reseed_game_rng(mut game_rng: ResMut<GameRng>, fixed_seed: Option<Res<FixedSeed>>) {
+    *game_rng = match fixed_seed {
+        Some(fixed_seed) => GameRng::new(fixed_seed.0),
+        None => GameRng::default(),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_seed_draws_the_same() {
+        let mut first = GameRng::new(7);
+        let mut second = GameRng::new(7);
+
+        assert_eq!(first.gen::<u64>(), second.gen::<u64>());
+        assert_eq!(first.drops().gen::<u64>(), second.drops().gen::<u64>());
+        assert_eq!(first.saucers().gen::<u64>(), second.saucers().gen::<u64>());
+    }
+
+    #[test]
+    fn drawing_from_one_stream_leaves_the_others_alone() {
+        let mut first = GameRng::new(7);
+        let mut second = GameRng::new(7);
+
+        for _ in 0..10 {
+            first.drops().gen::<u64>();
+            first.saucers().gen::<u64>();
+        }
+
+        assert_eq!(first.gen::<u64>(), second.gen::<u64>());
+    }
+
+    #[test]
+    fn streams_differ_from_each_other() {
+        let mut game_rng = GameRng::new(7);
+        let main: u64 = game_rng.gen();
+
+        assert_ne!(main, GameRng::new(7).drops().gen::<u64>());
+        assert_ne!(main, GameRng::new(7).saucers().gen::<u64>());
+    }
+}
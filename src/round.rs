@@ -0,0 +1,187 @@
+use bevy::{prelude::*, time::Stopwatch};
+use serde::{Deserialize, Serialize};
+use sickle_ui::prelude::*;
+
+use crate::{game_mode::GameMode, states::GameState, stats::Score};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<RoundMode>();
+    app.register_type::<RoundClock>();
+    app.init_resource::<RoundMode>();
+    app.init_resource::<RoundClock>();
+    app.add_systems(OnEnter(GameState::Playing), (reset_round_clock, spawn_round_hud));
+    app.add_systems(
+        Update,
+        (round_clock_tick, round_end_conditions, update_round_hud)
+            .chain()
+            .run_if(in_state(GameState::Playing)),
+    );
+}
+
+/// The goal of a round, picked on the title screen.
+///
+/// This is separate from the [`GameMode`], which is
+/// about who is playing, rather than what they play for.
+#[derive(
+    Resource, Reflect, Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq, Hash,
+)]
+#[reflect(Resource)]
+pub enum RoundMode {
+    /// Play until the ship is destroyed.
+    #[default]
+    Classic,
+    /// Score as much as possible before the time runs out.
+    TimeAttack,
+    /// Reach the target score as quickly as possible.
+    ScoreAttack,
+    /// Classic, on a round seeded from the date,
+    /// with one scored attempt a day.
+    DailyChallenge,
+    /// Practice, with nothing spawning by
+    /// itself and no way to be destroyed.
+    Sandbox,
+}
+
+impl RoundMode {
+    pub const ALL: [RoundMode; 5] = [
+        RoundMode::Classic,
+        RoundMode::TimeAttack,
+        RoundMode::ScoreAttack,
+        RoundMode::DailyChallenge,
+        RoundMode::Sandbox,
+    ];
+
+    pub const TIME_ATTACK_SECS: f32 = 180.;
+    pub const SCORE_ATTACK_TARGET: u32 = 100;
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            RoundMode::Classic => "Classic",
+            RoundMode::TimeAttack => "Time Attack",
+            RoundMode::ScoreAttack => "Score Attack",
+            RoundMode::DailyChallenge => "Daily Challenge",
+            RoundMode::Sandbox => "Sandbox",
+        }
+    }
+
+    /// The mode after this one, for cycling through them.
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|mode| mode == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// How long the current round has been going,
+/// and whether it was won rather than lost.
+///
+/// This runs on real time, so that slow
+/// motion doesn't stop the clock.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+pub struct RoundClock {
+    stopwatch: Stopwatch,
+    completed: bool,
+}
+
+impl RoundClock {
+    pub fn elapsed_secs(&self) -> f32 {
+        self.stopwatch.elapsed_secs()
+    }
+
+    /// Whether the round ended by reaching its goal, such as
+    /// the target score, rather than the ship being destroyed.
+    pub fn completed(&self) -> bool {
+        self.completed
+    }
+}
+
+/// Format seconds as minutes, seconds and tenths.
+pub fn format_secs(secs: f32) -> String {
+    let tenths = (secs.max(0.) * 10.) as u32;
+    format!("{}:{:02}.{}", tenths / 600, tenths / 10 % 60, tenths % 10)
+}
+
+fn reset_round_clock(mut clock: ResMut<RoundClock>) {
+    *clock = RoundClock::default();
+}
+
+fn round_clock_tick(mut clock: ResMut<RoundClock>, time: Res<Time<Real>>) {
+    clock.stopwatch.tick(time.delta());
+}
+
+fn round_end_conditions(
+    mut clock: ResMut<RoundClock>,
+    round_mode: Res<RoundMode>,
+    game_mode: Res<GameMode>,
+    score: Res<Score>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // Versus rounds have their own rules.
+    if game_mode.is_versus() {
+        return;
+    }
+
+    let completed = match *round_mode {
+        RoundMode::Classic | RoundMode::DailyChallenge | RoundMode::Sandbox => false,
+        RoundMode::TimeAttack => clock.elapsed_secs() >= RoundMode::TIME_ATTACK_SECS,
+        RoundMode::ScoreAttack => score.0 >= RoundMode::SCORE_ATTACK_TARGET,
+    };
+
+    if completed {
+        clock.completed = true;
+        clock.stopwatch.pause();
+        next_state.set(GameState::GameOver);
+    }
+}
+
+/// The score, and the clock if the mode has one,
+/// in the top left of the screen.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct RoundHud;
+
+fn spawn_round_hud(mut commands: Commands, game_mode: Res<GameMode>) {
+    if game_mode.is_versus() {
+        return;
+    }
+
+    commands
+        .ui_builder(UiRoot)
+        .column(|column| {
+            column.spawn((TextBundle::default(), RoundHud));
+        })
+        .style()
+        .position_type(PositionType::Absolute)
+        .top(Val::Px(16.))
+        .left(Val::Px(16.))
+        .entity_commands()
+        .insert(StateScoped(GameState::Playing));
+}
+
+fn update_round_hud(
+    mut hud_query: Query<&mut Text, With<RoundHud>>,
+    clock: Res<RoundClock>,
+    round_mode: Res<RoundMode>,
+    score: Res<Score>,
+) {
+    let value = match *round_mode {
+        RoundMode::Classic | RoundMode::DailyChallenge | RoundMode::Sandbox => {
+            format!("Score {}", score.0)
+        }
+        RoundMode::TimeAttack => format!(
+            "Score {}\n{}",
+            score.0,
+            format_secs(RoundMode::TIME_ATTACK_SECS - clock.elapsed_secs())
+        ),
+        RoundMode::ScoreAttack => format!(
+            "Score {}/{}\n{}",
+            score.0,
+            RoundMode::SCORE_ATTACK_TARGET,
+            format_secs(clock.elapsed_secs())
+        ),
+    };
+
+    for mut text in &mut hud_query {
+        *text = Text::from_section(value.clone(), TextStyle::default());
+    }
+}
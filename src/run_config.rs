@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<RunConfig>();
+    app.init_resource::<RunConfig>();
+}
+
+/// A change to the rules of a run, picked
+/// on the title screen before it starts.
+#[derive(Reflect, Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Modifier {
+    /// One health, no pickups and faster asteroids.
+    Hardcore,
+    /// Asteroids can't hurt, but the score isn't ranked.
+    Zen,
+    /// Rotation is inverted.
+    Mirror,
+}
+
+impl Modifier {
+    pub const ALL: [Modifier; 3] = [Modifier::Hardcore, Modifier::Zen, Modifier::Mirror];
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            Modifier::Hardcore => "Hardcore",
+            Modifier::Zen => "Zen",
+            Modifier::Mirror => "Mirror",
+        }
+    }
+}
+
+/// The modifiers of the current run, which the systems
+/// they affect read from, rather than each keeping a flag.
+#[derive(
+    Resource, Reflect, Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq,
+)]
+#[reflect(Resource)]
+pub struct RunConfig {
+    hardcore: bool,
+    zen: bool,
+    mirror: bool,
+}
+
+impl RunConfig {
+    pub fn has(&self, modifier: Modifier) -> bool {
+        match modifier {
+            Modifier::Hardcore => self.hardcore,
+            Modifier::Zen => self.zen,
+            Modifier::Mirror => self.mirror,
+        }
+    }
+
+    pub fn toggle(&mut self, modifier: Modifier) {
+        let flag = match modifier {
+            Modifier::Hardcore => &mut self.hardcore,
+            Modifier::Zen => &mut self.zen,
+            Modifier::Mirror => &mut self.mirror,
+        };
+        *flag = !*flag;
+    }
+
+    /// The active modifiers, by name, or `None` if there aren't any.
+    pub fn describe(&self) -> Option<String> {
+        let names: Vec<&str> = Modifier::ALL
+            .iter()
+            .filter(|modifier| self.has(**modifier))
+            .map(Modifier::get_name)
+            .collect();
+
+        (!names.is_empty()).then(|| names.join(", "))
+    }
+
+    pub fn player_health(&self) -> u16 {
+        if self.hardcore {
+            1
+        } else {
+            3
+        }
+    }
+
+    pub fn pickups_enabled(&self) -> bool {
+        !self.hardcore
+    }
+
+    /// What the asteroids' top speed is multiplied by.
+    pub fn asteroid_speed_scale(&self) -> f32 {
+        if self.hardcore {
+            1.5
+        } else {
+            1.
+        }
+    }
+
+    pub fn asteroids_harmless(&self) -> bool {
+        self.zen
+    }
+
+    /// Whether the run's score goes on the leaderboards.
+    pub fn is_ranked(&self) -> bool {
+        !self.zen
+    }
+
+    /// What the rotation input is multiplied by.
+    pub fn rotation_scale(&self) -> f32 {
+        if self.mirror {
+            -1.
+        } else {
+            1.
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use std::f32::consts::PI;
+
+use bevy::{color::palettes::css::YELLOW, prelude::*, window::PrimaryWindow};
+use rand::{thread_rng, Rng};
+use sickle_ui::prelude::*;
+
+use crate::{
+    asteroid::{asteroid_spawner::SpawnersEnabled, AsteroidID, AsteroidKind, SpawnAsteroid}, game_mode::GameMode, pickup::{PickupEffect, SpawnPickup}, power_up::PowerUp, round::RoundMode, states::GameState
+};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<SandboxControls>();
+    app.init_resource::<SandboxControls>();
+    app.add_systems(
+        OnEnter(GameState::Playing),
+        (disable_spawners, spawn_sandbox_hud).run_if(is_sandbox),
+    );
+    app.add_systems(
+        Update,
+        (
+            sandbox_hotkeys,
+            spawn_at_cursor,
+            visualize_spawn_direction,
+            update_sandbox_hud,
+        )
+            .chain()
+            .run_if(in_state(GameState::Playing).and_then(is_sandbox)),
+    );
+}
+
+/// Versus has rules of its own, so it's never a sandbox.
+fn is_sandbox(round_mode: Res<RoundMode>, game_mode: Res<GameMode>) -> bool {
+    *round_mode == RoundMode::Sandbox && !game_mode.is_versus()
+}
+
+/// Every pickup the sandbox can spawn, cycled through in order.
+const PICKUPS: [PickupEffect; 9] = [
+    PickupEffect::Heal(1),
+    PickupEffect::ExtraLife,
+    PickupEffect::PowerUp {
+        power_up: PowerUp::Shield,
+        secs: 8.,
+    },
+    PickupEffect::PowerUp {
+        power_up: PowerUp::RapidFire,
+        secs: 10.,
+    },
+    PickupEffect::PowerUp {
+        power_up: PowerUp::Multishot(3),
+        secs: 10.,
+    },
+    PickupEffect::PowerUp {
+        power_up: PowerUp::Piercing,
+        secs: 8.,
+    },
+    PickupEffect::PowerUp {
+        power_up: PowerUp::SlowMotion(0.5),
+        secs: 5.,
+    },
+    PickupEffect::PowerUp {
+        power_up: PowerUp::ScoreMultiplier(2),
+        secs: 15.,
+    },
+    PickupEffect::PowerUp {
+        power_up: PowerUp::Magnet(3.),
+        secs: 15.,
+    },
+];
+
+/// What the sandbox spawns at the cursor.
+#[derive(Resource, Reflect, Debug, Default)]
+#[reflect(Resource)]
+struct SandboxControls {
+    /// Index into [`AsteroidKind::ALL`].
+    kind: usize,
+    /// The angle, in radians, new asteroids head off at.
+    direction: f32,
+    /// Index into [`PICKUPS`].
+    pickup: usize,
+}
+
+impl SandboxControls {
+    fn kind(&self) -> AsteroidKind {
+        AsteroidKind::ALL[self.kind % AsteroidKind::ALL.len()]
+    }
+
+    fn pickup(&self) -> PickupEffect {
+        PICKUPS[self.pickup % PICKUPS.len()]
+    }
+}
+
+/// Nothing spawns by itself in the sandbox, until the spawners are turned on.
+fn disable_spawners(mut spawners_enabled: ResMut<SpawnersEnabled>) {
+    spawners_enabled.0 = false;
+}
+
+/// Where the cursor is in the world, if it's over the window.
+fn cursor_position(
+    window_query: &Query<&Window, With<PrimaryWindow>>,
+    camera_query: &Query<(&Camera, &GlobalTransform)>,
+) -> Option<Vec2> {
+    let cursor = window_query.get_single().ok()?.cursor_position()?;
+    let (camera, camera_transform) = camera_query.get_single().ok()?;
+    camera.viewport_to_world_2d(camera_transform, cursor)
+}
+
+/// The sandbox's hotkeys, which are listed in its HUD.
+///
+/// The number keys pick an asteroid kind, Q and E turn the
+/// direction it heads off in, and left click spawns it. Tab
+/// picks a pickup and right click spawns it. T turns the
+/// spawners on and off, and Escape leaves the sandbox.
+fn sandbox_hotkeys(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut controls: ResMut<SandboxControls>,
+    mut spawners_enabled: ResMut<SpawnersEnabled>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    const KIND_KEYS: [KeyCode; 5] = [
+        KeyCode::Digit1,
+        KeyCode::Digit2,
+        KeyCode::Digit3,
+        KeyCode::Digit4,
+        KeyCode::Digit5,
+    ];
+    const DIRECTION_STEP: f32 = PI / 12.;
+
+    for (kind, key) in KIND_KEYS.iter().enumerate() {
+        if keys.just_pressed(*key) {
+            controls.kind = kind;
+        }
+    }
+
+    if keys.just_pressed(KeyCode::KeyQ) {
+        controls.direction += DIRECTION_STEP;
+    }
+    if keys.just_pressed(KeyCode::KeyE) {
+        controls.direction -= DIRECTION_STEP;
+    }
+    if keys.just_pressed(KeyCode::Tab) {
+        controls.pickup = (controls.pickup + 1) % PICKUPS.len();
+    }
+    if keys.just_pressed(KeyCode::KeyT) {
+        spawners_enabled.0 = !spawners_enabled.0;
+    }
+    if keys.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Title);
+    }
+}
+
+/// Spawn the picked asteroid or pickup where the sandbox was clicked.
+fn spawn_at_cursor(
+    buttons: Res<ButtonInput<MouseButton>>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    controls: Res<SandboxControls>,
+    mut asteroid_id: ResMut<AsteroidID>,
+    mut commands: Commands,
+) {
+    let Some(position) = cursor_position(&window_query, &camera_query) else {
+        return;
+    };
+
+    if buttons.just_pressed(MouseButton::Left) {
+        commands.trigger(SpawnAsteroid::new(
+            controls.kind(),
+            Transform::from_translation(position.extend(0.0)),
+            Vec2::from_angle(controls.direction).extend(0.0),
+            asteroid_id.get(),
+            0,
+            // The sandbox isn't replayed, so it isn't seeded either.
+            thread_rng().gen(),
+        ));
+    }
+    if buttons.just_pressed(MouseButton::Right) {
+        commands.trigger(SpawnPickup::new(position.extend(0.0), controls.pickup()));
+    }
+}
+
+/// Show which way an asteroid spawned at the cursor would go.
+fn visualize_spawn_direction(
+    mut gizmos: Gizmos,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+    camera_query: Query<(&Camera, &GlobalTransform)>,
+    controls: Res<SandboxControls>,
+) {
+    const ARROW_LENGTH: f32 = 60.;
+
+    let Some(position) = cursor_position(&window_query, &camera_query) else {
+        return;
+    };
+
+    gizmos.arrow_2d(
+        position,
+        position + Vec2::from_angle(controls.direction) * ARROW_LENGTH,
+        YELLOW,
+    );
+}
+
+/// The hotkeys, and what they are set to, in the top right.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct SandboxHud;
+
+fn spawn_sandbox_hud(mut commands: Commands) {
+    commands
+        .ui_builder(UiRoot)
+        .column(|column| {
+            column.spawn((TextBundle::default(), SandboxHud));
+        })
+        .style()
+        .position_type(PositionType::Absolute)
+        .top(Val::Px(16.))
+        .right(Val::Px(16.))
+        .entity_commands()
+        .insert(StateScoped(GameState::Playing));
+}
+
+fn update_sandbox_hud(
+    mut hud_query: Query<&mut Text, With<SandboxHud>>,
+    controls: Res<SandboxControls>,
+    spawners_enabled: Res<SpawnersEnabled>,
+) {
+    let value = format!(
+        "[1-5] Asteroid: {}\n[Q/E] Direction: {:.0}\n[Tab] Pickup: {}\n[T] Spawners: {}\n[LMB/RMB] Spawn\n[Esc] Quit",
+        controls.kind().get_name(0),
+        controls.direction.to_degrees().rem_euclid(360.),
+        controls.pickup().get_name(),
+        if spawners_enabled.0 { "On" } else { "Off" },
+    );
+
+    for mut text in &mut hud_query {
+        *text = Text::from_section(value.clone(), TextStyle::default());
+    }
+}
@@ -2,7 +2,7 @@ pub use bevy::prelude::*;
 
 mod game_over;
 mod playing;
-mod title;
+pub mod title;
 
 pub fn plugin(app: &mut App) {
     app.add_plugins(game_over::plugin);
@@ -1,15 +1,22 @@
 use bevy::{
-    color::palettes::{css::BLACK, tailwind::GRAY_50},
+    color::palettes::{css::{BLACK, GOLD}, tailwind::GRAY_50},
+    ecs::system::SystemParam,
     prelude::*,
 };
 use sickle_ui::prelude::*;
 
-use crate::stats::Score;
+use crate::{
+    attract::is_attract_mode, daily::{DailyChallenge, DailyResults}, game_mode::GameMode, leaderboard::Leaderboards, round::{format_secs, RoundClock, RoundMode}, run_config::RunConfig, stats::Score, versus::VersusRound
+};
 
 use super::GameState;
 
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(GameState::GameOver), setup_game_over);
+    app.add_systems(
+        OnEnter(GameState::GameOver),
+        // A demo round goes straight back to the title screen.
+        setup_game_over.run_if(not(is_attract_mode)),
+    );
     app.add_systems(
         Update,
         (restart_button, title_button).run_if(in_state(GameState::GameOver)),
@@ -46,7 +53,33 @@ fn title_button(
     }
 }
 
-fn setup_game_over(mut commands: Commands, score: Res<Score>) {
+/// How the round that just ended went.
+#[derive(SystemParam)]
+struct RoundOutcome<'w> {
+    score: Res<'w, Score>,
+    round_clock: Res<'w, RoundClock>,
+    versus_round: Res<'w, VersusRound>,
+}
+
+/// The results the round is shown alongside.
+#[derive(SystemParam)]
+struct Records<'w> {
+    leaderboards: Res<'w, Leaderboards>,
+    daily_results: Res<'w, DailyResults>,
+    daily_challenge: Option<Res<'w, DailyChallenge>>,
+}
+
+fn setup_game_over(
+    mut commands: Commands,
+    game_mode: Res<GameMode>,
+    round_mode: Res<RoundMode>,
+    run_config: Res<RunConfig>,
+    outcome: RoundOutcome,
+    records: Records,
+) {
+    let RoundOutcome { score, round_clock, versus_round } = outcome;
+    let Records { leaderboards, daily_results, daily_challenge } = records;
+
     // UI root.
     commands
         .ui_builder(UiRoot)
@@ -57,14 +90,122 @@ fn setup_game_over(mut commands: Commands, score: Res<Score>) {
                 .style()
                 .margin(UiRect::top(Val::Vh(15.)));
 
-            // Score record.
-            column.row(|row| {
-                row.spawn(TextBundle::from("Asteroids Hit:"));
+            if game_mode.is_versus() {
+                // Versus results.
+                column.spawn(TextBundle::from(match versus_round.winner() {
+                    Some(number) => format!("Player {} Wins", number.0 + 1),
+                    None => String::from("Draw"),
+                }));
 
-                row.spawn(TextBundle::from(score.0.to_string()));
-            })
-                .style()
-                .justify_content(JustifyContent::SpaceEvenly);
+                for (number, kills) in versus_round.kills().iter().enumerate() {
+                    column.row(|row| {
+                        row.spawn(TextBundle::from(format!("Player {} Kills:", number + 1)));
+
+                        row.spawn(TextBundle::from(kills.to_string()));
+                    })
+                        .style()
+                        .justify_content(JustifyContent::SpaceEvenly);
+                }
+            } else {
+                // Score record.
+                column.spawn(TextBundle::from(round_mode.get_name()));
+
+                if let Some(modifiers) = run_config.describe() {
+                    column.spawn(TextBundle::from(modifiers));
+                }
+
+                column.row(|row| {
+                    row.spawn(TextBundle::from("Asteroids Hit:"));
+
+                    row.spawn(TextBundle::from(score.0.to_string()));
+                })
+                    .style()
+                    .justify_content(JustifyContent::SpaceEvenly);
+
+                if *round_mode == RoundMode::ScoreAttack {
+                    column.row(|row| {
+                        row.spawn(TextBundle::from("Time:"));
+
+                        row.spawn(TextBundle::from(match round_clock.completed() {
+                            true => format_secs(round_clock.elapsed_secs()),
+                            false => String::from("Target Missed"),
+                        }));
+                    })
+                        .style()
+                        .justify_content(JustifyContent::SpaceEvenly);
+                }
+
+                // The day's challenge, to compare with others who played it.
+                if let Some(challenge) = daily_challenge
+                    .filter(|_| *round_mode == RoundMode::DailyChallenge)
+                {
+                    column.spawn(TextBundle::from(format!("{}", challenge.day)));
+
+                    column.spawn(TextBundle::from(format!("Seed {:016X}", challenge.seed)));
+
+                    if let Some(result) = daily_results.get(challenge.day) {
+                        column.row(|row| {
+                            row.spawn(TextBundle::from("Today's Result:"));
+
+                            row.spawn(TextBundle::from(result.score.to_string()));
+
+                            if let Some(modifiers) = result.modifiers.describe() {
+                                row.spawn(TextBundle::from(modifiers));
+                            }
+                        })
+                            .style()
+                            .justify_content(JustifyContent::SpaceEvenly);
+                    }
+
+                    if !challenge.scored {
+                        column.spawn(TextBundle::from("Practice, only the first ranked solo attempt is scored"));
+                    }
+                }
+
+                // Leaderboard for the mode, with this round highlighted;
+                // only solo rounds are ranked, so only they show it.
+                let latest = leaderboards.latest(*round_mode);
+                let entries = match *game_mode {
+                    GameMode::Solo => leaderboards.get(*round_mode),
+                    _ => &[],
+                };
+                for (place, entry) in entries.iter().enumerate() {
+                    let value = match *round_mode {
+                        RoundMode::ScoreAttack => format_secs(entry.secs),
+                        RoundMode::Classic
+                        | RoundMode::TimeAttack
+                        | RoundMode::DailyChallenge
+                        | RoundMode::Sandbox => {
+                            entry.score.to_string()
+                        }
+                    };
+
+                    let color = match latest == Some(place) {
+                        true => GOLD,
+                        false => GRAY_50,
+                    };
+
+                    column.row(|row| {
+                        row.spawn(TextBundle::from(format!("{}.", place + 1)))
+                            .style()
+                            .font_color(color.into());
+
+                        row.spawn(TextBundle::from(value))
+                            .style()
+                            .font_color(color.into());
+
+                        // Shown on the entry, as modified rounds
+                        // are ranked alongside unmodified ones.
+                        if let Some(modifiers) = entry.modifiers.describe() {
+                            row.spawn(TextBundle::from(modifiers))
+                                .style()
+                                .font_color(color.into());
+                        }
+                    })
+                        .style()
+                        .justify_content(JustifyContent::SpaceEvenly);
+                }
+            }
 
             // Button menu.
             column
@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 
 use crate::{
-    player::SpawnPlayer, stats::Score, viewport_bound::SetupViewportCollider
+    game_mode::GameMode, player::SpawnPlayer, stats::Score, viewport_bound::SetupViewportCollider
 };
 use crate::asteroid::asteroid_spawner::{SetupAsteroidSpawners, SpawnAsteroids};
 
@@ -11,11 +11,13 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(GameState::Playing), setup_play_area);
 }
 
-fn setup_play_area(mut commands: Commands, mut score: ResMut<Score>) {
+fn setup_play_area(mut commands: Commands, mut score: ResMut<Score>, game_mode: Res<GameMode>) {
     // setup viewport collider
     commands.trigger(SetupViewportCollider);
-    // player
-    commands.trigger(SpawnPlayer);
+    // players
+    for number in 0..game_mode.player_count() {
+        commands.trigger(SpawnPlayer::new(number));
+    }
     // setup asteroid_spawners
     commands.trigger(SetupAsteroidSpawners);
     // spawn initial asteroids
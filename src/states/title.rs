@@ -4,6 +4,10 @@ use bevy::{
 };
 use sickle_ui::prelude::*;
 
+use crate::{
+    game_mode::GameMode, network::NetworkSession, round::RoundMode, run_config::{Modifier, RunConfig}
+};
+
 use super::GameState;
 
 
@@ -13,7 +17,15 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(GameState::Title), setup_title);
     app.add_systems(
         Update,
-        (exit_button, play_button).run_if(in_state(GameState::Title)),
+        (
+            exit_button,
+            play_button,
+            coop_button,
+            versus_button,
+            round_mode_button,
+            modifier_buttons,
+        )
+            .run_if(in_state(GameState::Title)),
     );
 }
 
@@ -24,14 +36,114 @@ struct PlayButton;
 fn play_button(
     query: Query<&Interaction, (Changed<Interaction>, With<PlayButton>)>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut game_mode: ResMut<GameMode>,
+    network_session: Option<Res<NetworkSession>>,
+) {
+    for interaction in &query {
+        if *interaction == Interaction::Pressed {
+            // Started with a network session, play goes online.
+            *game_mode = match &network_session {
+                Some(session) => GameMode::Online {
+                    players: session.player_count(),
+                },
+                None => GameMode::Solo,
+            };
+            next_state.set(GameState::Playing);
+        };
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct CoopButton;
+
+fn coop_button(
+    query: Query<&Interaction, (Changed<Interaction>, With<CoopButton>)>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut game_mode: ResMut<GameMode>,
 ) {
     for interaction in &query {
         if *interaction == Interaction::Pressed {
+            *game_mode = GameMode::Coop;
             next_state.set(GameState::Playing);
         };
     }
 }
 
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct VersusButton;
+
+/// Versus is for at least two players, and
+/// everyone with a gamepad plugged in can join.
+fn versus_button(
+    query: Query<&Interaction, (Changed<Interaction>, With<VersusButton>)>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut game_mode: ResMut<GameMode>,
+    gamepads: Res<Gamepads>,
+) {
+    const MAX_PLAYERS: usize = 4;
+
+    for interaction in &query {
+        if *interaction == Interaction::Pressed {
+            let players = gamepads.iter().count().clamp(2, MAX_PLAYERS) as u8;
+            *game_mode = GameMode::Versus { players };
+            next_state.set(GameState::Playing);
+        };
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct RoundModeButton;
+
+/// Cycles through the [`RoundMode`]s, which
+/// apply to solo and co-op, but not versus.
+fn round_mode_button(
+    query: Query<(&Interaction, &Children), (Changed<Interaction>, With<RoundModeButton>)>,
+    mut text_query: Query<&mut Text>,
+    mut round_mode: ResMut<RoundMode>,
+) {
+    for (interaction, children) in &query {
+        if *interaction == Interaction::Pressed {
+            *round_mode = round_mode.next();
+
+            let mut text_iter = text_query.iter_many_mut(children);
+            while let Some(mut text) = text_iter.fetch_next() {
+                text.sections[0].value = format!("Mode: {}", round_mode.get_name());
+            }
+        };
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ModifierButton(Modifier);
+
+impl ModifierButton {
+    fn get_label(&self, run_config: &RunConfig) -> String {
+        let state = if run_config.has(self.0) { "On" } else { "Off" };
+        format!("{}: {}", self.0.get_name(), state)
+    }
+}
+
+fn modifier_buttons(
+    query: Query<(&Interaction, &ModifierButton, &Children), Changed<Interaction>>,
+    mut text_query: Query<&mut Text>,
+    mut run_config: ResMut<RunConfig>,
+) {
+    for (interaction, button, children) in &query {
+        if *interaction == Interaction::Pressed {
+            run_config.toggle(button.0);
+
+            let mut text_iter = text_query.iter_many_mut(children);
+            while let Some(mut text) = text_iter.fetch_next() {
+                text.sections[0].value = button.get_label(&run_config);
+            }
+        };
+    }
+}
+
 #[derive(Component, Reflect)]
 #[reflect(Component)]
 struct ExitButton;
@@ -47,7 +159,20 @@ fn exit_button(
     }
 }
 
-fn setup_title(mut commands: Commands) {
+pub fn setup_title(mut commands: Commands, round_mode: Res<RoundMode>, run_config: Res<RunConfig>) {
+    spawn_title_menu(&mut commands, *round_mode, *run_config, GameState::Title);
+}
+
+/// The menu, showing the given settings, for as long as `state` lasts.
+///
+/// Its buttons only do anything on the title screen; elsewhere,
+/// such as over a demo round, it's just there to be seen.
+pub fn spawn_title_menu(
+    commands: &mut Commands,
+    round_mode: RoundMode,
+    run_config: RunConfig,
+    state: GameState,
+) {
     commands
         .ui_builder(UiRoot)
         .column(|column| {
@@ -68,6 +193,50 @@ fn setup_title(mut commands: Commands) {
                         .style()
                         .background_color(GRAY_50.into())
                         .padding(UiRect::horizontal(Val::Px(20.)));
+                    column
+                        .container((ButtonBundle::default(), CoopButton), |button| {
+                            button
+                                .spawn(TextBundle::from("Co-op"))
+                                .style()
+                                .font_color(BLACK.into());
+                        })
+                        .style()
+                        .background_color(GRAY_50.into())
+                        .padding(UiRect::horizontal(Val::Px(20.)));
+                    column
+                        .container((ButtonBundle::default(), VersusButton), |button| {
+                            button
+                                .spawn(TextBundle::from("Versus"))
+                                .style()
+                                .font_color(BLACK.into());
+                        })
+                        .style()
+                        .background_color(GRAY_50.into())
+                        .padding(UiRect::horizontal(Val::Px(20.)));
+                    column
+                        .container((ButtonBundle::default(), RoundModeButton), |button| {
+                            button
+                                .spawn(TextBundle::from(format!("Mode: {}", round_mode.get_name())))
+                                .style()
+                                .font_color(BLACK.into());
+                        })
+                        .style()
+                        .background_color(GRAY_50.into())
+                        .padding(UiRect::horizontal(Val::Px(20.)));
+                    for modifier in Modifier::ALL {
+                        let modifier_button = ModifierButton(modifier);
+                        let label = modifier_button.get_label(&run_config);
+                        column
+                            .container((ButtonBundle::default(), modifier_button), |button| {
+                                button
+                                    .spawn(TextBundle::from(label))
+                                    .style()
+                                    .font_color(BLACK.into());
+                            })
+                            .style()
+                            .background_color(GRAY_50.into())
+                            .padding(UiRect::horizontal(Val::Px(20.)));
+                    }
                     column
                         .container((ButtonBundle::default(), ExitButton), |button| {
                             button
@@ -90,5 +259,5 @@ fn setup_title(mut commands: Commands) {
         .align_items(AlignItems::Center)
         .margin(UiRect::all(Val::Auto))
         .entity_commands()
-        .insert(StateScoped(GameState::Title));
+        .insert(StateScoped(state));
 }
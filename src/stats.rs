@@ -1,6 +1,6 @@
 use std::cmp;
 
-use bevy::prelude::*;
+use bevy::{ecs::system::SystemParam, prelude::*};
 
 pub fn plugin(app: &mut App) {
     app.register_type::<LinearAcceleration>();
@@ -8,6 +8,7 @@ pub fn plugin(app: &mut App) {
     app.register_type::<Health>();
     app.register_type::<Points>();
     app.insert_resource(Score(10));
+    app.insert_resource(ScoreMultiplier(1));
 }
 
 #[derive(Component, Reflect)]
@@ -42,6 +43,11 @@ impl Health {
     pub fn current(&self) -> u16 {
         self.current
     }
+
+    /// Bring health back up to its max.
+    pub fn restore(&mut self) {
+        self.current = self.max;
+    }
 }
 
 #[derive(Component, Reflect)]
@@ -51,3 +57,22 @@ pub struct Points(pub u32);
 #[derive(Resource, Reflect)]
 #[reflect(Resource)]
 pub struct Score(pub u32);
+
+/// What any points scored are multiplied by.
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct ScoreMultiplier(pub u32);
+
+/// The [`Score`], along with the [`ScoreMultiplier`]
+/// that anything added to it is multiplied by.
+#[derive(SystemParam)]
+pub struct Scoring<'w> {
+    score: ResMut<'w, Score>,
+    multiplier: Res<'w, ScoreMultiplier>,
+}
+
+impl Scoring<'_> {
+    pub fn add(&mut self, points: &Points) {
+        self.score.0 += points.0 * self.multiplier.0;
+    }
+}
@@ -0,0 +1,155 @@
+use bevy::prelude::*;
+use sickle_ui::prelude::*;
+
+use crate::{
+    faction::{Faction, FactionMatrix}, game_mode::GameMode, player::{PlayerNumber, PlayerShotDown}, states::GameState
+};
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<VersusRound>();
+    app.init_resource::<VersusRound>();
+    app.add_systems(
+        OnEnter(GameState::Playing),
+        (configure_friendly_fire, (reset_versus_round, spawn_versus_hud).run_if(is_versus)),
+    );
+    app.add_systems(
+        Update,
+        (count_kills, versus_round_tick, update_versus_hud)
+            .chain()
+            .run_if(in_state(GameState::Playing).and_then(is_versus)),
+    );
+}
+
+fn is_versus(game_mode: Res<GameMode>) -> bool {
+    game_mode.is_versus()
+}
+
+/// The kills each player has scored, and the time
+/// left, in a versus round; whichever runs out first,
+/// the time or the kills needed, ends the round.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct VersusRound {
+    /// Indexed by [`PlayerNumber`].
+    kills: Vec<u32>,
+    timer: Timer,
+}
+
+impl Default for VersusRound {
+    fn default() -> Self {
+        Self {
+            kills: vec![],
+            timer: Timer::from_seconds(Self::ROUND_SECS, TimerMode::Once),
+        }
+    }
+}
+
+impl VersusRound {
+    const ROUND_SECS: f32 = 180.;
+    const KILL_LIMIT: u32 = 10;
+
+    pub fn kills(&self) -> &[u32] {
+        &self.kills
+    }
+
+    /// The player with the most kills,
+    /// or `None` if it's a draw.
+    pub fn winner(&self) -> Option<PlayerNumber> {
+        let most = *self.kills.iter().max()?;
+        let mut leaders = self.kills.iter().enumerate().filter(|(_, kills)| **kills == most);
+
+        match (leaders.next(), leaders.next()) {
+            (Some((number, _)), None) => Some(PlayerNumber(number as u8)),
+            _ => None,
+        }
+    }
+
+    fn is_over(&self) -> bool {
+        self.timer.finished() || self.kills.iter().any(|kills| *kills >= Self::KILL_LIMIT)
+    }
+}
+
+/// Players can only shoot each other in versus.
+fn configure_friendly_fire(game_mode: Res<GameMode>, mut faction_matrix: ResMut<FactionMatrix>) {
+    faction_matrix.set(Faction::Player, Faction::Player, game_mode.is_versus());
+}
+
+fn reset_versus_round(mut round: ResMut<VersusRound>, game_mode: Res<GameMode>) {
+    *round = VersusRound {
+        kills: vec![0; game_mode.player_count() as usize],
+        ..default()
+    };
+}
+
+fn count_kills(
+    mut player_shot_down_event_reader: EventReader<PlayerShotDown>,
+    player_query: Query<&PlayerNumber>,
+    mut round: ResMut<VersusRound>,
+) {
+    for event in player_shot_down_event_reader.read() {
+        let Ok(PlayerNumber(number)) = player_query.get(event.shooter) else {
+            continue;
+        };
+
+        if let Some(kills) = round.kills.get_mut(*number as usize) {
+            *kills += 1;
+        }
+    }
+}
+
+fn versus_round_tick(
+    mut round: ResMut<VersusRound>,
+    time: Res<Time>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    round.timer.tick(time.delta());
+    if round.is_over() {
+        next_state.set(GameState::GameOver);
+    }
+}
+
+/// The kill counts and time left along the top of the screen.
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct VersusHud;
+
+fn spawn_versus_hud(mut commands: Commands) {
+    commands
+        .ui_builder(UiRoot)
+        .row(|row| {
+            row.spawn((TextBundle::default(), VersusHud));
+        })
+        .style()
+        .position_type(PositionType::Absolute)
+        .top(Val::Px(16.))
+        .width(Val::Percent(100.))
+        .justify_content(JustifyContent::Center)
+        .entity_commands()
+        .insert(StateScoped(GameState::Playing));
+}
+
+fn update_versus_hud(mut hud_query: Query<&mut Text, With<VersusHud>>, round: Res<VersusRound>) {
+    let remaining = round.timer.remaining_secs().ceil() as u32;
+    let mut sections: Vec<TextSection> = round
+        .kills
+        .iter()
+        .enumerate()
+        .map(|(number, kills)| {
+            TextSection::new(
+                format!("P{} {}    ", number + 1, kills),
+                TextStyle {
+                    color: PlayerNumber(number as u8).get_color(),
+                    ..default()
+                },
+            )
+        })
+        .collect();
+    sections.push(TextSection::new(
+        format!("{}:{:02}", remaining / 60, remaining % 60),
+        TextStyle::default(),
+    ));
+
+    for mut text in &mut hud_query {
+        text.sections.clone_from(&sections);
+    }
+}
@@ -35,6 +35,9 @@ fn setup_viewport_collider(_: Trigger<SetupViewportCollider>, mut commands: Comm
         ViewportCollider,
         StateScoped(GameState::Playing),
         Collider::rectangle(VIEWPORT_WIDTH, VIEWPORT_HEIGHT),
+        // Only used to detect leaving the viewport,
+        // so it should never push dynamic bodies around.
+        Sensor,
     ));
 }
 
@@ -0,0 +1,61 @@
+use bevy::prelude::*;
+
+use crate::states::GameState;
+
+pub fn plugin(app: &mut App) {
+    app.register_type::<Wave>();
+    app.init_resource::<Wave>();
+    app.add_event::<WaveStarted>();
+    app.add_systems(OnEnter(GameState::Playing), reset_wave);
+}
+
+/// How far through the round the player is.
+///
+/// A wave is over once enough asteroid families,
+/// every piece split off of a spawned asteroid,
+/// have been cleared from the play area.
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct Wave {
+    number: u32,
+    families_cleared: u32,
+}
+
+impl Default for Wave {
+    fn default() -> Self {
+        Self {
+            number: 1,
+            families_cleared: 0,
+        }
+    }
+}
+
+impl Wave {
+    const FAMILIES_PER_WAVE: u32 = 8;
+
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    /// Record an asteroid family being cleared.
+    ///
+    /// Returns `true` if that started the next wave.
+    pub fn clear_family(&mut self) -> bool {
+        self.families_cleared += 1;
+        if self.families_cleared < Self::FAMILIES_PER_WAVE {
+            return false;
+        }
+
+        self.families_cleared = 0;
+        self.number += 1;
+        true
+    }
+}
+
+/// Sent with the number of the wave that just started.
+#[derive(Event, Debug)]
+pub struct WaveStarted(pub u32);
+
+fn reset_wave(mut wave: ResMut<Wave>) {
+    *wave = Wave::default();
+}